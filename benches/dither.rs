@@ -0,0 +1,75 @@
+//! Compares the wall-clock time of Floyd-Steinberg error diffusion against ordered Bayer
+//! dithering, on a 160x48 render, about the size of a full terminal printed with halfblocks.
+//!
+//! Run with `cargo bench --bench dither`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use bevy_ratatui_render::{apply_dither, apply_floyd_steinberg, ColorDepth, DitherMode};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+const WIDTH: u32 = 160;
+const HEIGHT: u32 = 48;
+const ITERATIONS: u32 = 500;
+
+/// Standard VGA values of the 16 basic ANSI colors.
+const PALETTE: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xaa, 0x00, 0x00],
+    [0x00, 0xaa, 0x00],
+    [0xaa, 0x55, 0x00],
+    [0x00, 0x00, 0xaa],
+    [0xaa, 0x00, 0xaa],
+    [0x00, 0xaa, 0xaa],
+    [0xaa, 0xaa, 0xaa],
+    [0x55, 0x55, 0x55],
+    [0xff, 0x55, 0x55],
+    [0x55, 0xff, 0x55],
+    [0xff, 0xff, 0x55],
+    [0x55, 0x55, 0xff],
+    [0xff, 0x55, 0xff],
+    [0x55, 0xff, 0xff],
+    [0xff, 0xff, 0xff],
+];
+
+fn main() {
+    let gradient = DynamicImage::ImageRgba8(RgbaImage::from_fn(WIDTH, HEIGHT, |x, y| {
+        Rgba([
+            (x * 255 / (WIDTH - 1)) as u8,
+            (y * 255 / (HEIGHT - 1)) as u8,
+            ((x + y) * 255 / (WIDTH + HEIGHT - 2)) as u8,
+            255,
+        ])
+    }));
+
+    let bayer = time(&gradient, |img| {
+        apply_dither(img, DitherMode::Bayer4x4, ColorDepth::Colors16)
+    });
+    let floyd_steinberg = time(&gradient, |img| apply_floyd_steinberg(img, &PALETTE));
+
+    println!("{WIDTH}x{HEIGHT}, mean of {ITERATIONS} iterations:");
+    println!("  bayer 4x4:       {bayer:?}");
+    println!("  floyd-steinberg: {floyd_steinberg:?}");
+    println!(
+        "  floyd-steinberg is {:.1}x slower",
+        floyd_steinberg.as_secs_f64() / bayer.as_secs_f64()
+    );
+}
+
+/// Mean time `dither` takes on a fresh copy of the image, excluding the copy itself.
+fn time(image: &DynamicImage, mut dither: impl FnMut(&mut DynamicImage)) -> Duration {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let mut img = image.clone();
+        let start = Instant::now();
+        dither(black_box(&mut img));
+        total += start.elapsed();
+        black_box(&img);
+    }
+
+    total / ITERATIONS
+}
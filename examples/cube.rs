@@ -11,7 +11,7 @@ use bevy_ratatui::event::KeyEvent;
 use bevy_ratatui::kitty::KittyEnabled;
 use bevy_ratatui::terminal::RatatuiContext;
 use bevy_ratatui::RatatuiPlugins;
-use bevy_ratatui_render::{RatatuiRenderContext, RatatuiRenderPlugin};
+use bevy_ratatui_render::{ImageCopier, RatatuiRenderContext, RatatuiRenderPlugin};
 use crossterm::event::{KeyCode, KeyEventKind};
 use ratatui::layout::Alignment;
 use ratatui::style::Style;
@@ -47,6 +47,7 @@ fn main() {
         .add_systems(Startup, setup_scene_system)
         .add_systems(Update, draw_scene_system.map(error))
         .add_systems(Update, handle_input_system)
+        .add_systems(Update, toggle_copier_system)
         .add_systems(Update, rotate_cube_system.after(handle_input_system))
         .run();
 }
@@ -108,6 +109,7 @@ fn draw_scene_system(
             .title_bottom("[q for quit]")
             .title_bottom("[d for debug]")
             .title_bottom("[p for panic]")
+            .title_bottom("[s for pause]")
             .title_alignment(Alignment::Center);
 
         let inner = block.inner(frame.size());
@@ -193,6 +195,20 @@ pub fn handle_input_system(
     }
 }
 
+fn toggle_copier_system(mut rat_events: EventReader<KeyEvent>, image_copiers: Query<&ImageCopier>) {
+    for KeyEvent(key_event) in rat_events.read() {
+        if let (KeyEventKind::Press, KeyCode::Char('s')) = (key_event.kind, key_event.code) {
+            for image_copier in &image_copiers {
+                if image_copier.enabled() {
+                    image_copier.disable();
+                } else {
+                    image_copier.enable();
+                }
+            }
+        }
+    }
+}
+
 fn rotate_cube_system(
     time: Res<Time>,
     mut cube: Query<&mut Transform, With<Cube>>,
@@ -0,0 +1,98 @@
+use std::io;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::window::ExitCondition;
+use bevy::{app::ScheduleRunnerPlugin, prelude::*};
+use bevy_ratatui::event::KeyEvent;
+use bevy_ratatui::RatatuiPlugins;
+use bevy_ratatui_render::{RatatuiRenderContext, RatatuiRenderPlugin};
+use crossterm::event::{KeyCode, KeyEventKind};
+
+#[derive(Component)]
+pub struct Cube;
+
+fn main() -> io::Result<()> {
+    let terminal_size = crossterm::terminal::size()?;
+
+    App::new()
+        .add_plugins((
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                }),
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1. / 60.)),
+            RatatuiPlugins::default(),
+            RatatuiRenderPlugin::from_terminal_size("main", terminal_size)
+                .autoresize()
+                .print_full_terminal(),
+        ))
+        .insert_resource(ClearColor(Color::rgb(0., 0., 0.)))
+        .add_systems(Startup, setup_scene_system)
+        .add_systems(Update, handle_input_system)
+        .add_systems(Update, rotate_cube_system.after(handle_input_system))
+        .run();
+
+    Ok(())
+}
+
+fn setup_scene_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    ratatui_render: Res<RatatuiRenderContext>,
+) {
+    commands.spawn((
+        Cube,
+        PbrBundle {
+            mesh: meshes.add(Cuboid::default()),
+            material: materials.add(StandardMaterial {
+                base_color: bevy::prelude::Color::rgb(100. / 256., 140. / 256., 180. / 256.),
+                ..Default::default()
+            }),
+            transform: Transform::default(),
+            ..Default::default()
+        },
+    ));
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(15., 15., 1.)),
+        material: materials.add(StandardMaterial::default()),
+        transform: Transform::from_xyz(0., 0., -6.),
+        ..Default::default()
+    });
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(3., 4., 6.),
+        ..default()
+    });
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(3., 3., 3.).looking_at(Vec3::ZERO, Vec3::Z),
+        tonemapping: Tonemapping::None,
+        camera: Camera {
+            target: ratatui_render.target("main").unwrap(),
+            ..default()
+        },
+        ..default()
+    });
+}
+
+pub fn handle_input_system(mut rat_events: EventReader<KeyEvent>, mut exit: EventWriter<AppExit>) {
+    for KeyEvent(key_event) in rat_events.read() {
+        if let KeyEventKind::Press | KeyEventKind::Repeat = key_event.kind {
+            if let KeyCode::Char('q') = key_event.code {
+                exit.send(AppExit);
+            }
+        }
+    }
+}
+
+fn rotate_cube_system(time: Res<Time>, mut cube: Query<&mut Transform, With<Cube>>) {
+    cube.single_mut().rotate_z(time.delta_seconds());
+}
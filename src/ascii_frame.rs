@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use image::imageops::FilterType;
+
+use crate::{
+    headless::{image_to_dynamic, receive_rendered_images_system},
+    image_to_ascii, AsciiConfig, RatatuiRenderContext,
+};
+
+/// Converts each new render of the provided id into rows of ascii characters, kept in the
+/// `AsciiFrame` resource for systems that print or process the text themselves.
+///
+/// As with `RatatuiRenderStrategy::Ascii`, each character covers a 1x2 block of pixels of the
+/// render, as character cells are about twice as tall as they are wide.
+///
+/// # example:
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ratatui::RatatuiPlugins;
+/// # use bevy_ratatui_render::{AsciiFramePlugin, RatatuiRenderPlugin};
+/// #
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     RatatuiPlugins::default(),
+///     RatatuiRenderPlugin::new("main", (80, 48)),
+///     AsciiFramePlugin {
+///         id: "main".into(),
+///         config: default(),
+///     },
+/// ));
+/// ```
+pub struct AsciiFramePlugin {
+    /// Id of the render to convert, as passed to `RatatuiRenderPlugin::new`.
+    pub id: String,
+    /// Ramp of characters the render is converted with.
+    pub config: AsciiConfig,
+}
+
+impl Plugin for AsciiFramePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AsciiFrame>().add_systems(
+            First,
+            ascii_frame_system_generator(self.id.clone(), self.config.clone())
+                .after(receive_rendered_images_system),
+        );
+    }
+}
+
+/// Rows of ascii characters converted from the most recent render by `AsciiFramePlugin`. Empty
+/// until the first render is received.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct AsciiFrame(pub Vec<String>);
+
+/// Converts the render for the provided id into the `AsciiFrame` resource whenever a new render
+/// is received.
+fn ascii_frame_system_generator(
+    id: String,
+    config: AsciiConfig,
+) -> impl FnMut(Res<RatatuiRenderContext>, ResMut<AsciiFrame>) {
+    let mut last_frame_index = None;
+
+    move |ratatui_render, mut ascii_frame| {
+        let Some(render_pipe) = ratatui_render.get(&id) else {
+            return;
+        };
+
+        let frame_index = render_pipe.frame_index();
+        if frame_index.is_none() || frame_index == last_frame_index {
+            return;
+        }
+        last_frame_index = frame_index;
+
+        match image_to_dynamic(&render_pipe.image) {
+            Ok(image) => {
+                let image = image.resize_exact(
+                    image.width(),
+                    image.height().div_ceil(2),
+                    FilterType::Triangle,
+                );
+                ascii_frame.0 = image_to_ascii(&image, &config);
+            }
+            Err(e) => error!("failed to convert render {id} to ascii: {e:?}"),
+        }
+    }
+}
@@ -3,9 +3,12 @@
 //
 // https://github.com/bevyengine/bevy/blob/main/examples/app/headless_renderer.rs
 
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use bevy::{
@@ -20,58 +23,247 @@ use bevy::{
             TextureUsages,
         },
         renderer::{RenderContext, RenderDevice, RenderQueue},
-        texture::{BevyDefault, TextureFormatPixelInfo},
+        texture::{BevyDefault, IntoDynamicImageError, TextureFormatPixelInfo},
+        view::RenderLayers,
         Extract,
     },
 };
 use crossbeam_channel::{Receiver, Sender};
-
-use crate::RatatuiRenderContext;
-
+use image::{DynamicImage, Rgb32FImage, Rgba32FImage, RgbaImage};
+
+use crate::{ColorConfig, RatatuiRenderContext, RatatuiRenderReady, RatatuiRenderStrategy};
+
+/// Usages given to render textures unless configured otherwise. `COPY_SRC` is always added on top of
+/// whatever is configured, since the copy node reads from the texture.
+pub const DEFAULT_RENDER_TEXTURE_USAGES: TextureUsages = TextureUsages::COPY_SRC
+    .union(TextureUsages::RENDER_ATTACHMENT)
+    .union(TextureUsages::TEXTURE_BINDING);
+
+/// Render texture formats that `image_to_dynamic` can convert for printing.
+pub const SUPPORTED_RENDER_TEXTURE_FORMATS: [TextureFormat; 7] = [
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Rgba16Float,
+    TextureFormat::Rgba32Float,
+    TextureFormat::R32Float,
+];
+
+/// Render world resource holding the copiers extracted this frame, which the copy node reads
+/// from. Iterate it to inspect them from render world systems.
 #[derive(Clone, Default, Resource, Deref, DerefMut)]
 pub struct ImageCopiers(pub Vec<ImageCopier>);
 
+impl<'a> IntoIterator for &'a ImageCopiers {
+    type Item = &'a ImageCopier;
+    type IntoIter = std::slice::Iter<'a, ImageCopier>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ImageCopiers {
+    type Item = &'a mut ImageCopier;
+    type IntoIter = std::slice::IterMut<'a, ImageCopier>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+/// A render copied out of the GPU, sent from the render world to the main world.
+pub struct RenderedFrame {
+    /// Pixel data, with rows padded to `RenderDevice::align_copy_bytes_per_row`.
+    pub data: Vec<u8>,
+    /// When the render was copied out of the GPU.
+    pub timestamp: Instant,
+    /// Number of renders copied by the same copier before this one.
+    pub frame_index: u64,
+    /// Dimensions, (width, height), of the texture the render was copied from.
+    pub dimensions: (u32, u32),
+}
+
+impl RenderedFrame {
+    /// Time elapsed since the render was copied out of the GPU.
+    pub fn age(&self) -> Duration {
+        self.timestamp.elapsed()
+    }
+}
+
 #[derive(Clone, Component)]
 pub struct ImageCopier {
-    sender: Sender<Vec<u8>>,
+    sender: Sender<RenderedFrame>,
     buffer: Buffer,
     enabled: Arc<AtomicBool>,
+    /// Cleared by `RatatuiRenderPlugin::snapshot_every` on frames it skips. Kept apart from
+    /// `enabled` so the schedule never overrides `disable()`.
+    scheduled: Arc<AtomicBool>,
+    bytes_transferred: Arc<AtomicU64>,
+    frame_index: Arc<AtomicU64>,
+    /// Set by the copy node once it has copied this frame's render into the buffer. Reset for each
+    /// extracted copier, so that only buffers with a fresh render are sent to the main world.
+    copied: Arc<AtomicBool>,
     src_image: Handle<Image>,
+    /// Dimensions of the texture the buffer is sized for. Renders of any other size are skipped.
+    dimensions: (u32, u32),
+    capture_layers: Option<RenderLayers>,
 }
 
 impl ImageCopier {
     pub fn new(
         src_image: Handle<Image>,
-        (width, height): (u32, u32),
+        dimensions: (u32, u32),
+        format: TextureFormat,
         render_device: &RenderDevice,
-        sender: Sender<Vec<u8>>,
+        sender: Sender<RenderedFrame>,
+        capture_layers: Option<RenderLayers>,
     ) -> ImageCopier {
-        let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row((width) as usize) * 4;
-
-        let cpu_buffer = render_device.create_buffer(&BufferDescriptor {
-            label: None,
-            size: padded_bytes_per_row as u64 * height as u64,
-            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         ImageCopier {
-            buffer: cpu_buffer,
+            buffer: create_copier_buffer(
+                Self::estimated_memory_usage(dimensions, format),
+                render_device,
+            ),
             src_image,
             enabled: Arc::new(AtomicBool::new(true)),
+            scheduled: Arc::new(AtomicBool::new(true)),
+            bytes_transferred: Arc::new(AtomicU64::new(0)),
+            frame_index: Arc::new(AtomicU64::new(0)),
+            copied: Arc::new(AtomicBool::new(false)),
+            dimensions,
             sender,
+            capture_layers,
         }
     }
 
+    /// Creates a copier for a texture in `TextureFormat::bevy_default()`, the format render
+    /// textures use unless `RatatuiRenderPlugin::render_texture_format` overrides it.
+    pub fn new_rgba8(
+        src_image: Handle<Image>,
+        dimensions: (u32, u32),
+        render_device: &RenderDevice,
+        sender: Sender<RenderedFrame>,
+        capture_layers: Option<RenderLayers>,
+    ) -> ImageCopier {
+        Self::new(
+            src_image,
+            dimensions,
+            TextureFormat::bevy_default(),
+            render_device,
+            sender,
+            capture_layers,
+        )
+    }
+
+    /// Reallocates the buffer for a texture of the provided format at the same dimensions. The
+    /// copier keeps sending on the same channel and sharing the same enabled flag.
+    pub fn with_format(self, format: TextureFormat, render_device: &RenderDevice) -> ImageCopier {
+        self.resized(self.dimensions, format, render_device)
+    }
+
+    /// Size in bytes of the GPU buffer that `new` allocates for a texture of the given dimensions
+    /// and format, including the padding wgpu requires at the end of each row.
+    pub fn estimated_memory_usage((width, height): (u32, u32), format: TextureFormat) -> u64 {
+        let padded_bytes_per_row =
+            RenderDevice::align_copy_bytes_per_row(width as usize * format.pixel_size());
+
+        padded_bytes_per_row as u64 * height as u64
+    }
+
+    /// Whether renders are currently being copied out of the GPU.
     pub fn enabled(&self) -> bool {
-        self.enabled.load(Ordering::Relaxed)
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// Resumes copying renders out of the GPU after `disable()`.
+    ///
+    /// The flag is stored with `Ordering::Release` and read with `Ordering::Acquire` when the
+    /// copier is extracted, so anything written before calling this is visible to the render
+    /// world by the time it starts copying again. The change takes effect from the next extract.
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Stops copying renders out of the GPU, leaving the ratatui widget showing the last copied
+    /// render. Useful to save the cost of the GPU readback while the render is not on screen, for
+    /// example while showing a menu.
+    ///
+    /// Takes effect from the next extract, so a render already extracted this frame may still be
+    /// copied. See `enable()` for the memory ordering.
+    pub fn disable(&self) {
+        self.enabled.store(false, Ordering::Release);
+    }
+
+    /// Creates a copier for the same image with a buffer sized for new dimensions, sending on the
+    /// same channel and sharing the same enabled flag.
+    fn resized(
+        &self,
+        dimensions: (u32, u32),
+        format: TextureFormat,
+        render_device: &RenderDevice,
+    ) -> ImageCopier {
+        ImageCopier {
+            buffer: create_copier_buffer(
+                Self::estimated_memory_usage(dimensions, format),
+                render_device,
+            ),
+            dimensions,
+            ..self.clone()
+        }
+    }
+
+    /// Total number of bytes copied from the GPU and sent to the main world by this copier, and
+    /// any clones of it, since it was created or last reset.
+    pub fn total_bytes_transferred(&self) -> u64 {
+        self.bytes_transferred.load(Ordering::Relaxed)
+    }
+
+    /// Resets the count returned by `total_bytes_transferred()` to zero.
+    pub fn reset_transfer_counter(&self) {
+        self.bytes_transferred.store(0, Ordering::Relaxed);
+    }
+
+    /// Clones the copier with a newly allocated buffer of the same size, rather than sharing the
+    /// original's buffer as `clone()` does. The clone sends on the same channel and shares the
+    /// same enabled flag and transfer counter.
+    pub fn clone_with_new_buffer(&self, render_device: &RenderDevice) -> ImageCopier {
+        ImageCopier {
+            buffer: create_copier_buffer(self.buffer.size(), render_device),
+            ..self.clone()
+        }
+    }
+
+    /// Whether an active camera rendering to this copier's image also renders one of its capture
+    /// layers. Copiers without capture layers always capture.
+    fn captures(&self, cameras: &Query<(&Camera, Option<&RenderLayers>)>) -> bool {
+        let Some(capture_layers) = self.capture_layers else {
+            return true;
+        };
+
+        cameras.iter().any(|(camera, layers)| {
+            let RenderTarget::Image(handle) = &camera.target else {
+                return false;
+            };
+
+            camera.is_active
+                && *handle == self.src_image
+                && layers
+                    .copied()
+                    .unwrap_or_default()
+                    .intersects(&capture_layers)
+        })
     }
 }
 
 pub struct HeadlessRenderPipe {
-    receiver: Receiver<Vec<u8>>,
+    receiver: Receiver<RenderedFrame>,
+    last_frame: Option<(Instant, u64)>,
+    copier_scheduled: Arc<AtomicBool>,
     pub target: RenderTarget,
     pub image: Image,
+    pub strategy: RatatuiRenderStrategy,
+    pub color_config: ColorConfig,
 }
 
 impl HeadlessRenderPipe {
@@ -80,36 +272,153 @@ impl HeadlessRenderPipe {
         images: &mut ResMut<Assets<Image>>,
         render_device: &Res<RenderDevice>,
         dimensions: (u32, u32),
+        format: TextureFormat,
+        usages: TextureUsages,
+        capture_layers: Option<RenderLayers>,
     ) -> Self {
         let (sender, receiver) = crossbeam_channel::unbounded();
 
-        let (render_texture, cpu_texture) = create_render_textures(dimensions);
+        let (render_texture, cpu_texture) = create_render_textures(dimensions, format, usages);
 
+        let format = render_texture.texture_descriptor.format;
         let render_handle = images.add(render_texture);
 
-        commands.spawn(ImageCopier::new(
+        let image_copier = ImageCopier::new(
             render_handle.clone(),
             dimensions,
+            format,
             render_device,
             sender,
-        ));
+            capture_layers,
+        );
+        let copier_scheduled = image_copier.scheduled.clone();
+
+        commands.spawn(image_copier);
 
         Self {
             receiver,
+            last_frame: None,
+            copier_scheduled,
             target: RenderTarget::Image(render_handle),
             image: cpu_texture,
+            strategy: RatatuiRenderStrategy::default(),
+            color_config: ColorConfig::default(),
+        }
+    }
+
+    /// Resizes the render texture, the image it is copied into, and the buffer of the copier between
+    /// them. Renders still in flight at the old size are dropped rather than copied.
+    pub fn resize(
+        &mut self,
+        dimensions: (u32, u32),
+        images: &mut Assets<Image>,
+        render_device: &RenderDevice,
+        image_copiers: &mut Query<&mut ImageCopier>,
+    ) {
+        let RenderTarget::Image(render_handle) = &self.target else {
+            return;
+        };
+
+        if dimensions == (self.image.width(), self.image.height()) {
+            return;
         }
+
+        let (width, height) = dimensions;
+        let size = Extent3d {
+            width,
+            height,
+            ..Default::default()
+        };
+
+        if let Some(render_texture) = images.get_mut(render_handle) {
+            render_texture.resize(size);
+        }
+        self.image.resize(size);
+
+        for mut image_copier in image_copiers.iter_mut() {
+            if image_copier.src_image == *render_handle {
+                *image_copier = image_copier.resized(
+                    dimensions,
+                    self.image.texture_descriptor.format,
+                    render_device,
+                );
+            }
+        }
+    }
+
+    /// Whether at least one render has been received into this pipe's image. Until then the image
+    /// is blank.
+    pub fn is_ready(&self) -> bool {
+        self.last_frame.is_some()
+    }
+
+    /// Time elapsed since the render currently in this pipe's image was copied out of the GPU, or
+    /// None if no render has been received yet.
+    pub fn frame_age(&self) -> Option<Duration> {
+        self.last_frame.map(|(timestamp, _)| timestamp.elapsed())
+    }
+
+    /// Index of the render currently in this pipe's image, counting every render copied out of the
+    /// GPU for this pipe, or None if no render has been received yet.
+    pub fn frame_index(&self) -> Option<u64> {
+        self.last_frame.map(|(_, frame_index)| frame_index)
+    }
+
+    /// Sets whether the current frame is one that `snapshot_every` copies. A render is only copied
+    /// on scheduled frames while the copier is also enabled.
+    pub(crate) fn set_scheduled(&self, scheduled: bool) {
+        self.copier_scheduled.store(scheduled, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+impl HeadlessRenderPipe {
+    /// A pipe receiving 2x2 `Rgba8UnormSrgb` renders on the returned sender, whose rows are padded
+    /// to 256 bytes, without a copier on the GPU.
+    pub(crate) fn detached() -> (Self, Sender<RenderedFrame>) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (_, image) = create_render_textures(
+            (2, 2),
+            TextureFormat::Rgba8UnormSrgb,
+            TextureUsages::empty(),
+        );
+
+        let render_pipe = Self {
+            receiver,
+            last_frame: None,
+            copier_scheduled: Arc::new(AtomicBool::new(true)),
+            target: RenderTarget::Image(Handle::default()),
+            image,
+            strategy: RatatuiRenderStrategy::default(),
+            color_config: ColorConfig::default(),
+        };
+
+        (render_pipe, sender)
+    }
+
+    /// Whether `snapshot_every` has scheduled the copier for the current frame.
+    pub(crate) fn scheduled(&self) -> bool {
+        self.copier_scheduled.load(Ordering::Acquire)
     }
 }
 
 pub fn image_copier_extract_system(
     mut commands: Commands,
     image_copy_sources: Extract<Query<&ImageCopier>>,
+    cameras: Extract<Query<(&Camera, Option<&RenderLayers>)>>,
 ) {
     commands.insert_resource(ImageCopiers(
         image_copy_sources
             .iter()
-            .cloned()
+            .filter(|image_copy_source| {
+                image_copy_source.enabled()
+                    && image_copy_source.scheduled.load(Ordering::Acquire)
+                    && image_copy_source.captures(&cameras)
+            })
+            .map(|image_copy_source| ImageCopier {
+                copied: Arc::new(AtomicBool::new(false)),
+                ..image_copy_source.clone()
+            })
             .collect::<Vec<ImageCopier>>(),
     ));
 }
@@ -118,8 +427,9 @@ pub fn send_rendered_image_system(
     image_copy_sources: Res<ImageCopiers>,
     render_device: Res<RenderDevice>,
 ) {
-    for image_copy_source in image_copy_sources.iter() {
-        if !image_copy_source.enabled() {
+    for image_copy_source in &*image_copy_sources {
+        // Skipped by the copy node, so the buffer holds nothing new.
+        if !image_copy_source.copied.load(Ordering::Relaxed) {
             continue;
         }
 
@@ -127,35 +437,77 @@ pub fn send_rendered_image_system(
 
         let (s, r) = crossbeam_channel::bounded(1);
 
-        buffer_slice.map_async(MapMode::Read, move |r| match r {
-            Ok(r) => s.send(r).expect("Failed to send map update"),
-            Err(err) => panic!("Failed to map buffer {err}"),
+        buffer_slice.map_async(MapMode::Read, move |result| {
+            // The receiver is only dropped once the result below has been received.
+            let _ = s.send(result);
         });
 
         render_device.poll(Maintain::wait()).panic_on_timeout();
 
-        r.recv().expect("Failed to receive the map_async message");
+        match r.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                error!("failed to map render buffer: {err}");
+                continue;
+            }
+            Err(_) => {
+                error!("render buffer mapping was dropped before completing");
+                continue;
+            }
+        }
 
-        let _ = image_copy_source
-            .sender
-            .send(buffer_slice.get_mapped_range().to_vec());
+        let frame = RenderedFrame {
+            data: buffer_slice.get_mapped_range().to_vec(),
+            timestamp: Instant::now(),
+            frame_index: image_copy_source
+                .frame_index
+                .fetch_add(1, Ordering::Relaxed),
+            dimensions: image_copy_source.dimensions,
+        };
+        let len = frame.data.len() as u64;
+        if image_copy_source.sender.send(frame).is_ok() {
+            image_copy_source
+                .bytes_transferred
+                .fetch_add(len, Ordering::Relaxed);
+        }
 
         image_copy_source.buffer.unmap();
     }
 }
 
-pub fn receive_rendered_images_system(mut ratatui_render: ResMut<RatatuiRenderContext>) {
-    for render_pipe in &mut ratatui_render.values_mut() {
-        let mut image_data = Vec::new();
-        while let Ok(data) = render_pipe.receiver.try_recv() {
-            image_data = data;
+pub fn receive_rendered_images_system(
+    mut ratatui_render: ResMut<RatatuiRenderContext>,
+    mut ready: EventWriter<RatatuiRenderReady>,
+) {
+    for (id, render_pipe) in ratatui_render.iter_mut() {
+        let mut frame = None;
+        while let Ok(received) = render_pipe.receiver.try_recv() {
+            frame = Some(received);
         }
-        if !image_data.is_empty() {
+        if let Some(RenderedFrame {
+            data: image_data,
+            timestamp,
+            frame_index,
+            dimensions,
+        }) = frame
+        {
+            if dimensions != (render_pipe.image.width(), render_pipe.image.height()) {
+                // Rendered before the image was last resized.
+                continue;
+            }
+
             let row_bytes = render_pipe.image.width() as usize
                 * render_pipe.image.texture_descriptor.format.pixel_size();
             let aligned_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+            if image_data.len() != aligned_row_bytes * render_pipe.image.height() as usize {
+                continue;
+            }
+            if !render_pipe.is_ready() {
+                ready.send(RatatuiRenderReady { id: id.clone() });
+            }
+            render_pipe.last_frame = Some((timestamp, frame_index));
             if row_bytes == aligned_row_bytes {
-                render_pipe.image.data.clone_from(&image_data);
+                render_pipe.image.data = image_data;
             } else {
                 render_pipe.image.data = image_data
                     .chunks(aligned_row_bytes)
@@ -181,26 +533,32 @@ impl Node for ImageCopyNode {
         render_context: &mut RenderContext,
         world: &World,
     ) -> Result<(), NodeRunError> {
-        let image_copy_sources = world.get_resource::<ImageCopiers>().unwrap();
-        let gpu_images = world.get_resource::<RenderAssets<Image>>().unwrap();
-
-        for image_copy_source in image_copy_sources.iter() {
-            if !image_copy_source.enabled() {
+        // Absent until the first extract has run.
+        let Some(image_copy_sources) = world.get_resource::<ImageCopiers>() else {
+            return Ok(());
+        };
+        let gpu_images = world.resource::<RenderAssets<Image>>();
+        let render_queue = world.resource::<RenderQueue>();
+
+        for image_copy_source in image_copy_sources {
+            // The image asset may have been removed since the copier was extracted.
+            let Some(src_image) = gpu_images.get(&image_copy_source.src_image) else {
                 continue;
-            }
-
-            let src_image = gpu_images.get(&image_copy_source.src_image).unwrap();
+            };
 
             let mut encoder = render_context
                 .render_device()
                 .create_command_encoder(&CommandEncoderDescriptor::default());
 
-            let block_dimensions = src_image.texture_format.block_dimensions();
-            let block_size = src_image.texture_format.block_copy_size(None).unwrap();
-
-            let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row(
-                (src_image.size.x as usize / block_dimensions.0 as usize) * block_size as usize,
-            );
+            let Some(padded_bytes_per_row) =
+                padded_bytes_per_row(src_image.texture_format, src_image.size.x as u32)
+            else {
+                error!(
+                    "cannot copy a render in {:?}, which has no fixed block size",
+                    src_image.texture_format
+                );
+                continue;
+            };
 
             let texture_extent = Extent3d {
                 width: src_image.size.x as u32,
@@ -208,32 +566,183 @@ impl Node for ImageCopyNode {
                 depth_or_array_layers: 1,
             };
 
+            // The texture and the copier's buffer are resized separately, skip frames in between.
+            // Comparing dimensions rather than byte counts also catches resizes to the same area.
+            if (texture_extent.width, texture_extent.height) != image_copy_source.dimensions
+                || padded_bytes_per_row as u64 * texture_extent.height as u64
+                    != image_copy_source.buffer.size()
+            {
+                continue;
+            }
+
             encoder.copy_texture_to_buffer(
                 src_image.texture.as_image_copy(),
                 ImageCopyBuffer {
                     buffer: &image_copy_source.buffer,
                     layout: ImageDataLayout {
                         offset: 0,
-                        bytes_per_row: Some(
-                            std::num::NonZeroU32::new(padded_bytes_per_row as u32)
-                                .unwrap()
-                                .into(),
-                        ),
+                        bytes_per_row: Some(padded_bytes_per_row as u32),
                         rows_per_image: None,
                     },
                 },
                 texture_extent,
             );
 
-            let render_queue = world.get_resource::<RenderQueue>().unwrap();
             render_queue.submit(std::iter::once(encoder.finish()));
+
+            image_copy_source.copied.store(true, Ordering::Relaxed);
         }
 
         Ok(())
     }
 }
 
-fn create_render_textures(dimensions: (u32, u32)) -> (Image, Image) {
+/// Bytes per row of a texture of the provided format and width once copied into a buffer, padded as
+/// wgpu requires. None for formats without a fixed block size, such as depth formats.
+fn padded_bytes_per_row(format: TextureFormat, width: u32) -> Option<usize> {
+    let (block_width, _) = format.block_dimensions();
+    let block_size = format.block_copy_size(None)?;
+
+    Some(RenderDevice::align_copy_bytes_per_row(
+        (width / block_width) as usize * block_size as usize,
+    ))
+}
+
+fn create_copier_buffer(size: u64, render_device: &RenderDevice) -> Buffer {
+    render_device.create_buffer(&BufferDescriptor {
+        label: None,
+        size,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Converts a received render into a `DynamicImage`, for the formats in
+/// `SUPPORTED_RENDER_TEXTURE_FORMATS`. Linear formats are encoded to sRGB first, so that they print
+/// with the same brightness as the sRGB formats. Float values outside `0..1` are clipped once the
+/// image is converted to 8-bit color for printing.
+///
+/// `R32Float` renders, such as depth, ambient occlusion or distance field maps, are printed in
+/// grayscale with the value copied to all three channels. They hold data rather than color, so they
+/// are not encoded to sRGB.
+pub fn image_to_dynamic(image: &Image) -> Result<DynamicImage, IntoDynamicImageError> {
+    let (width, height) = (image.width(), image.height());
+    let format = image.texture_descriptor.format;
+    let data = &image.data;
+
+    let converted = match format {
+        TextureFormat::Rgba8UnormSrgb => {
+            RgbaImage::from_raw(width, height, data.clone()).map(DynamicImage::ImageRgba8)
+        }
+        TextureFormat::Bgra8UnormSrgb => {
+            RgbaImage::from_raw(width, height, bgra_to_rgba(data)).map(DynamicImage::ImageRgba8)
+        }
+        TextureFormat::Rgba8Unorm => {
+            RgbaImage::from_raw(width, height, linear_u8_to_srgb(data.clone()))
+                .map(DynamicImage::ImageRgba8)
+        }
+        TextureFormat::Bgra8Unorm => {
+            RgbaImage::from_raw(width, height, linear_u8_to_srgb(bgra_to_rgba(data)))
+                .map(DynamicImage::ImageRgba8)
+        }
+        TextureFormat::Rgba16Float => Rgba32FImage::from_raw(
+            width,
+            height,
+            linear_f32_to_srgb(
+                data.chunks_exact(2)
+                    .map(|bytes| f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]))),
+            ),
+        )
+        .map(DynamicImage::ImageRgba32F),
+        TextureFormat::Rgba32Float => Rgba32FImage::from_raw(
+            width,
+            height,
+            linear_f32_to_srgb(
+                data.chunks_exact(4)
+                    .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            ),
+        )
+        .map(DynamicImage::ImageRgba32F),
+        TextureFormat::R32Float => Rgb32FImage::from_raw(
+            width,
+            height,
+            data.chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .flat_map(|value| [value; 3])
+                .collect(),
+        )
+        .map(DynamicImage::ImageRgb32F),
+        _ => return Err(IntoDynamicImageError::UnsupportedFormat(format)),
+    };
+
+    converted.ok_or(IntoDynamicImageError::UnknownConversionError(format))
+}
+
+/// Swaps the red and blue channels of each pixel.
+fn bgra_to_rgba(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(4)
+        .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+        .collect()
+}
+
+/// Encodes linear rgba bytes with the sRGB transfer function. Alpha is left linear.
+fn linear_u8_to_srgb(mut data: Vec<u8>) -> Vec<u8> {
+    for pixel in data.chunks_exact_mut(4) {
+        for channel in &mut pixel[..3] {
+            *channel = (linear_to_srgb(*channel as f32 / 255.) * 255.).round() as u8;
+        }
+    }
+    data
+}
+
+/// Encodes linear rgba values with the sRGB transfer function. Alpha, every fourth value, is left
+/// linear.
+fn linear_f32_to_srgb(values: impl Iterator<Item = f32>) -> Vec<f32> {
+    values
+        .enumerate()
+        .map(|(index, value)| {
+            if index % 4 == 3 {
+                value
+            } else {
+                linear_to_srgb(value)
+            }
+        })
+        .collect()
+}
+
+/// The sRGB transfer function, from a linear value to its encoded value.
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Widens an IEEE 754 half precision float, given as its bits.
+fn f16_to_f32(bits: u16) -> f32 {
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    let magnitude = match exponent {
+        0 => mantissa as f32 * 2f32.powi(-24),
+        0x1f if mantissa == 0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => f32::from_bits(((exponent as u32 + 112) << 23) | (mantissa << 13)),
+    };
+
+    if bits & 0x8000 == 0 {
+        magnitude
+    } else {
+        -magnitude
+    }
+}
+
+fn create_render_textures(
+    dimensions: (u32, u32),
+    format: TextureFormat,
+    usages: TextureUsages,
+) -> (Image, Image) {
     let (width, height) = dimensions;
     let size = Extent3d {
         width,
@@ -244,15 +753,224 @@ fn create_render_textures(dimensions: (u32, u32)) -> (Image, Image) {
     let mut render_texture = Image::new_fill(
         size,
         TextureDimension::D2,
-        &[0; 4],
-        TextureFormat::bevy_default(),
+        &vec![0; format.pixel_size()],
+        format,
         RenderAssetUsages::default(),
     );
 
     let cpu_texture = render_texture.clone();
 
-    render_texture.texture_descriptor.usage |=
-        TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+    render_texture.texture_descriptor.usage |= usages | TextureUsages::COPY_SRC;
 
     (render_texture, cpu_texture)
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn test_world(render_pipe: HeadlessRenderPipe) -> World {
+        let mut ratatui_render = RatatuiRenderContext::default();
+        ratatui_render.insert("main".into(), render_pipe);
+
+        let mut world = World::new();
+        world.insert_resource(ratatui_render);
+        world.init_resource::<Events<RatatuiRenderReady>>();
+        world
+    }
+
+    fn frame(data: Vec<u8>, frame_index: u64) -> RenderedFrame {
+        RenderedFrame {
+            data,
+            timestamp: Instant::now(),
+            frame_index,
+            dimensions: (2, 2),
+        }
+    }
+
+    fn ready_count(world: &World) -> usize {
+        let events = world.resource::<Events<RatatuiRenderReady>>();
+        events.get_reader().read(events).count()
+    }
+
+    fn single_pixel(format: TextureFormat, data: Vec<u8>) -> Image {
+        Image::new(
+            Extent3d::default(),
+            TextureDimension::D2,
+            data,
+            format,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    fn to_rgba8(format: TextureFormat, data: Vec<u8>) -> [u8; 4] {
+        let image = image_to_dynamic(&single_pixel(format, data)).unwrap();
+        image.to_rgba8().get_pixel(0, 0).0
+    }
+
+    fn to_rgba32f(format: TextureFormat, data: Vec<u8>) -> [f32; 4] {
+        let image = image_to_dynamic(&single_pixel(format, data)).unwrap();
+        image.to_rgba32f().get_pixel(0, 0).0
+    }
+
+    fn assert_approx_eq(actual: [f32; 4], expected: [f32; 4]) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-5, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn srgb_formats_convert_as_is() {
+        let format = TextureFormat::Rgba8UnormSrgb;
+        assert_eq!(to_rgba8(format, vec![10, 20, 30, 40]), [10, 20, 30, 40]);
+
+        let format = TextureFormat::Bgra8UnormSrgb;
+        assert_eq!(to_rgba8(format, vec![30, 20, 10, 40]), [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn linear_formats_are_encoded_to_srgb() {
+        let format = TextureFormat::Rgba8Unorm;
+        assert_eq!(to_rgba8(format, vec![0, 64, 128, 128]), [0, 137, 188, 128]);
+
+        let format = TextureFormat::Bgra8Unorm;
+        assert_eq!(to_rgba8(format, vec![128, 64, 255, 128]), [255, 137, 188, 128]);
+    }
+
+    #[test]
+    fn float_formats_are_encoded_to_srgb() {
+        // 1.0, 0.5, 0.0 and an alpha of 0.5 as half precision floats.
+        let half = [0x3c00u16, 0x3800, 0x0000, 0x3800];
+        let data = half.iter().flat_map(|bits| bits.to_le_bytes()).collect();
+        assert_approx_eq(to_rgba32f(TextureFormat::Rgba16Float, data), [1., 0.735357, 0., 0.5]);
+
+        let float = [1f32, 0.5, 0., 0.5];
+        let data = float.iter().flat_map(|value| value.to_le_bytes()).collect();
+        assert_approx_eq(to_rgba32f(TextureFormat::Rgba32Float, data), [1., 0.735357, 0., 0.5]);
+    }
+
+    #[test]
+    fn single_channel_floats_are_printed_in_grayscale() {
+        let image = image_to_dynamic(&single_pixel(
+            TextureFormat::R32Float,
+            0.25f32.to_le_bytes().to_vec(),
+        ))
+        .unwrap();
+        assert_eq!(image.to_rgb32f().get_pixel(0, 0).0, [0.25; 3]);
+    }
+
+    #[test]
+    fn unsupported_formats_are_rejected() {
+        let image = single_pixel(TextureFormat::R8Unorm, vec![0]);
+        assert!(matches!(
+            image_to_dynamic(&image),
+            Err(IntoDynamicImageError::UnsupportedFormat(TextureFormat::R8Unorm))
+        ));
+
+        let mut image = single_pixel(TextureFormat::Rgba8UnormSrgb, vec![0; 4]);
+        image.data.pop();
+        assert!(matches!(
+            image_to_dynamic(&image),
+            Err(IntoDynamicImageError::UnknownConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn f16_to_f32_widens_normal_values() {
+        assert_eq!(f16_to_f32(0x3c00), 1.);
+        assert_eq!(f16_to_f32(0x3800), 0.5);
+        assert_eq!(f16_to_f32(0x7bff), 65504.);
+    }
+
+    #[test]
+    fn f16_to_f32_widens_subnormal_values() {
+        assert_eq!(f16_to_f32(0x0000), 0.);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0x03ff), 1023. * 2f32.powi(-24));
+    }
+
+    #[test]
+    fn f16_to_f32_widens_infinity_and_nan() {
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+        assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+
+    #[test]
+    fn f16_to_f32_widens_negative_values() {
+        assert_eq!(f16_to_f32(0xc000), -2.);
+        assert_eq!(f16_to_f32(0x8001), -(2f32.powi(-24)));
+        assert!(f16_to_f32(0x8000).is_sign_negative());
+    }
+
+    #[test]
+    fn padded_bytes_per_row_aligns_to_256() {
+        let format = TextureFormat::Rgba8UnormSrgb;
+        assert_eq!(padded_bytes_per_row(format, 64), Some(256));
+        assert_eq!(padded_bytes_per_row(format, 65), Some(512));
+        assert_eq!(padded_bytes_per_row(TextureFormat::Rgba32Float, 16), Some(256));
+        assert_eq!(padded_bytes_per_row(TextureFormat::Depth24Plus, 64), None);
+    }
+
+    #[test]
+    fn receives_latest_frame_without_padding() {
+        let (render_pipe, sender) = HeadlessRenderPipe::detached();
+        let mut world = test_world(render_pipe);
+
+        let mut data = vec![0; 512];
+        data[..8].fill(2);
+        data[256..264].fill(3);
+        sender.send(frame(vec![1; 512], 0)).unwrap();
+        sender.send(frame(data, 1)).unwrap();
+        world.run_system_once(receive_rendered_images_system);
+
+        let render_pipe = &world.resource::<RatatuiRenderContext>()["main"];
+        assert_eq!(render_pipe.frame_index(), Some(1));
+        assert_eq!(render_pipe.image.data, [[2; 8], [3; 8]].concat());
+    }
+
+    #[test]
+    fn ignores_frames_of_the_wrong_size() {
+        let (render_pipe, sender) = HeadlessRenderPipe::detached();
+        let mut world = test_world(render_pipe);
+
+        sender.send(frame(vec![1; 16], 0)).unwrap();
+        world.run_system_once(receive_rendered_images_system);
+
+        assert!(!world.resource::<RatatuiRenderContext>()["main"].is_ready());
+        assert_eq!(ready_count(&world), 0);
+    }
+
+    #[test]
+    fn ignores_frames_of_other_dimensions_with_the_same_size() {
+        let (render_pipe, sender) = HeadlessRenderPipe::detached();
+        let mut world = test_world(render_pipe);
+
+        // A 1x2 render also pads to 256 bytes per row, so only the dimensions tell it apart.
+        sender
+            .send(RenderedFrame {
+                dimensions: (1, 2),
+                ..frame(vec![1; 512], 0)
+            })
+            .unwrap();
+        world.run_system_once(receive_rendered_images_system);
+
+        assert!(!world.resource::<RatatuiRenderContext>()["main"].is_ready());
+    }
+
+    #[test]
+    fn sends_ready_once() {
+        let (render_pipe, sender) = HeadlessRenderPipe::detached();
+        let mut world = test_world(render_pipe);
+
+        for frame_index in 0..3 {
+            sender.send(frame(vec![0; 512], frame_index)).unwrap();
+            world.run_system_once(receive_rendered_images_system);
+        }
+
+        assert!(world.resource::<RatatuiRenderContext>()["main"].is_ready());
+        assert_eq!(ready_count(&world), 1);
+    }
+}
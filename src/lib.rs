@@ -1,6 +1,21 @@
+mod ascii_frame;
 mod headless;
 mod plugin;
+mod recording;
+mod renderers;
+mod statistics;
 mod widget;
 
-pub use plugin::{RatatuiRenderContext, RatatuiRenderPlugin};
-pub use widget::RatatuiRenderWidget;
+pub use ascii_frame::{AsciiFrame, AsciiFramePlugin};
+pub use headless::{ImageCopier, ImageCopiers, RenderedFrame};
+pub use plugin::{
+    terminal_pixel_size, ForceRedraw, RatatuiRenderContext, RatatuiRenderPlugin,
+    RatatuiRenderReady,
+};
+pub use recording::{GifRecorder, GifRecordingPlugin};
+pub use renderers::{
+    apply_dither, apply_floyd_steinberg, image_to_ascii, image_to_braille, AsciiConfig,
+    BrailleConfig, ColorConfig, ColorDepth, ColorMetric, Colors16Palette, DitherMode,
+};
+pub use statistics::FrameStatisticsPlugin;
+pub use widget::{RatatuiRenderStrategy, RatatuiRenderWidget};
@@ -1,20 +1,28 @@
-use std::io;
+use std::{io, time::Duration};
 
 use bevy::{
     prelude::*,
     render::{
-        camera::RenderTarget, render_graph::RenderGraph, renderer::RenderDevice, Render, RenderApp,
-        RenderSet,
+        camera::RenderTarget,
+        render_graph::RenderGraph,
+        render_resource::{TextureFormat, TextureUsages},
+        renderer::RenderDevice,
+        texture::BevyDefault,
+        view::RenderLayers,
+        Render, RenderApp, RenderSet,
     },
     utils::{error, hashbrown::HashMap},
+    window::RequestRedraw,
 };
-use bevy_ratatui::terminal::RatatuiContext;
+use bevy_ratatui::{event::ResizeEvent, terminal::RatatuiContext};
 
 use crate::{
     headless::{
         image_copier_extract_system, receive_rendered_images_system, send_rendered_image_system,
-        HeadlessRenderPipe, ImageCopy, ImageCopyNode,
+        HeadlessRenderPipe, ImageCopier, ImageCopy, ImageCopyNode, DEFAULT_RENDER_TEXTURE_USAGES,
+        SUPPORTED_RENDER_TEXTURE_FORMATS,
     },
+    ColorConfig, ColorDepth, ColorMetric, Colors16Palette, DitherMode, RatatuiRenderStrategy,
     RatatuiRenderWidget,
 };
 
@@ -63,8 +71,18 @@ use crate::{
 pub struct RatatuiRenderPlugin {
     label: String,
     dimensions: (u32, u32),
+    terminal_size: Option<(u16, u16)>,
+    cell_pixel_width: Option<u32>,
+    cell_pixel_height: Option<u32>,
     print_full_terminal: bool,
     disabled: bool,
+    strategy: RatatuiRenderStrategy,
+    color_config: ColorConfig,
+    snapshot_every: Option<u32>,
+    render_texture_usages: TextureUsages,
+    render_texture_format: TextureFormat,
+    capture_layers: Option<RenderLayers>,
+    autoresize: bool,
 }
 
 impl RatatuiRenderPlugin {
@@ -79,11 +97,51 @@ impl RatatuiRenderPlugin {
         Self {
             label: label.into(),
             dimensions,
+            terminal_size: None,
+            cell_pixel_width: None,
+            cell_pixel_height: None,
             print_full_terminal: false,
             disabled: false,
+            strategy: RatatuiRenderStrategy::default(),
+            color_config: ColorConfig::default(),
+            snapshot_every: None,
+            render_texture_usages: DEFAULT_RENDER_TEXTURE_USAGES,
+            render_texture_format: TextureFormat::bevy_default(),
+            capture_layers: None,
+            autoresize: false,
         }
     }
 
+    /// Create an instance of RatatuiRenderPlugin sized in terminal character cells rather than
+    /// pixels. The texture dimensions are the terminal size multiplied by the number of pixels
+    /// printed per cell, which by default matches the print strategy (1x2 for halfblocks, 2x4 for
+    /// braille) so that each pixel of the render maps to exactly one printed pixel.
+    ///
+    /// * `label` - Unique descriptive identifier, as in `new(label, dimensions)`.
+    ///
+    /// * `terminal_size` - (columns, rows) - the number of character cells the render will fill.
+    /// Zero columns or rows are treated as one, as the render texture cannot be empty.
+    pub fn from_terminal_size(label: &str, (columns, rows): (u16, u16)) -> Self {
+        Self {
+            terminal_size: Some((columns.max(1), rows.max(1))),
+            ..Self::new(label, (0, 0))
+        }
+    }
+
+    /// Overrides the number of pixels rendered across each character cell when sized with
+    /// `from_terminal_size`. Higher values supersample the render before it is printed.
+    pub fn cell_pixel_width(mut self, width: u32) -> Self {
+        self.cell_pixel_width = Some(width);
+        self
+    }
+
+    /// Overrides the number of pixels rendered down each character cell when sized with
+    /// `from_terminal_size`. Higher values supersample the render before it is printed.
+    pub fn cell_pixel_height(mut self, height: u32) -> Self {
+        self.cell_pixel_height = Some(height);
+        self
+    }
+
     /// Initializes RatatuiRenderContext resource but skips setting up the headless rendering.
     /// `target(id)` and `widget(id)` on the context resource will each return None.
     ///
@@ -107,22 +165,153 @@ impl RatatuiRenderPlugin {
         self.print_full_terminal = true;
         self
     }
+
+    /// Sets the strategy the ratatui widget uses to print the render to the terminal. Defaults to
+    /// `RatatuiRenderStrategy::Halfblocks`.
+    pub fn strategy(mut self, strategy: RatatuiRenderStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the range of colors the ratatui widget prints with, for terminals that do not support
+    /// 24-bit color. Each pixel is printed as the nearest color available at that depth. Defaults
+    /// to `ColorDepth::TrueColor`.
+    pub fn color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_config.depth = depth;
+        self
+    }
+
+    /// Sets the rgb values the terminal displays for each of the 16 basic ANSI colors, which
+    /// renders are matched against with `ColorDepth::Colors16`. Defaults to the standard VGA
+    /// palette, use `Colors16Palette::from_rgb_pairs` to match a custom terminal theme.
+    pub fn colors16_palette(mut self, palette: Colors16Palette) -> Self {
+        self.color_config.palette_16 = palette;
+        self
+    }
+
+    /// Sets how the nearest displayable color is picked when quantizing to a `color_depth()` other
+    /// than `ColorDepth::TrueColor`. Defaults to `ColorMetric::EuclideanRgb`.
+    pub fn color_metric(mut self, metric: ColorMetric) -> Self {
+        self.color_config.metric = metric;
+        self
+    }
+
+    /// Sets the dithering applied before colors are quantized to a `color_depth()` other than
+    /// `ColorDepth::TrueColor`, to smooth out banding in gradients. Defaults to `DitherMode::None`.
+    pub fn dither(mut self, mode: DitherMode) -> Self {
+        self.color_config.dither = mode;
+        self
+    }
+
+    /// Only copies a render into the ratatui widget once every `frames` frames, starting with the
+    /// first frame. The widget keeps showing the last copied render in between. Useful for
+    /// periodic snapshots, or to save the cost of the GPU readback on frames that are not needed.
+    /// Disabling the copier with `ImageCopier::disable()` still stops copying on scheduled frames.
+    ///
+    /// * `frames` - number of frames per copied render. `1` copies every frame.
+    pub fn snapshot_every(mut self, frames: u32) -> Self {
+        self.snapshot_every = Some(frames.max(1));
+        self
+    }
+
+    /// Overrides the `TextureUsages` of the texture that bevy renders into. Defaults to
+    /// `COPY_SRC | RENDER_ATTACHMENT | TEXTURE_BINDING`.
+    ///
+    /// Needed when something other than a camera writes to the render target, for example a
+    /// compute pass that requires `STORAGE_BINDING`. `COPY_SRC` is always added, as the render is
+    /// copied out of the texture each frame.
+    pub fn render_texture_usages(mut self, usages: TextureUsages) -> Self {
+        self.render_texture_usages = usages;
+        self
+    }
+
+    /// Overrides the `TextureFormat` of the texture that bevy renders into. Defaults to
+    /// `TextureFormat::bevy_default()`, usually `Rgba8UnormSrgb`.
+    ///
+    /// Useful for HDR pipelines (`Rgba16Float`, `Rgba32Float`) or hardware without support for the
+    /// default format. Renders must be convertible to an 8-bit image for printing, which is
+    /// supported for the 8-bit rgba and bgra formats, the 16 and 32-bit float rgba formats, and
+    /// `R32Float`, which prints in grayscale. Linear color formats are encoded to sRGB before
+    /// printing. Building the plugin panics for any other format.
+    pub fn render_texture_format(mut self, format: TextureFormat) -> Self {
+        self.render_texture_format = format;
+        self
+    }
+
+    /// Only copies the render to the ratatui widget while an active camera targeting it renders
+    /// at least one of the provided layers. Cameras without a `RenderLayers` component are treated
+    /// as rendering the default layer.
+    ///
+    /// Useful when several cameras share a render target and only some of them should update the
+    /// terminal output.
+    pub fn capture_layers(mut self, layers: RenderLayers) -> Self {
+        self.capture_layers = Some(layers);
+        self
+    }
+
+    /// Resizes the render to fill the terminal whenever bevy_ratatui sends a `ResizeEvent`. The
+    /// texture dimensions follow the terminal size the same way as with `from_terminal_size`,
+    /// including any `cell_pixel_width` or `cell_pixel_height`.
+    ///
+    /// Cameras targeting the render pick up the new aspect ratio automatically, but anything else
+    /// sized to the render texture needs to handle `ResizeEvent` itself.
+    pub fn autoresize(mut self) -> Self {
+        self.autoresize = true;
+        self
+    }
+
+    /// Dimensions of the render texture, computed from the terminal size when one was provided.
+    fn dimensions(&self) -> (u32, u32) {
+        let Some((columns, rows)) = self.terminal_size else {
+            return self.dimensions;
+        };
+
+        let (cell_width, cell_height) = self.cell_pixel_size();
+
+        (columns as u32 * cell_width, rows as u32 * cell_height)
+    }
+
+    /// Number of pixels rendered in each character cell, (width, height), using the overrides if
+    /// provided or otherwise matching the print strategy.
+    fn cell_pixel_size(&self) -> (u32, u32) {
+        let (cell_width, cell_height) = self.strategy.cell_pixel_size();
+
+        (
+            self.cell_pixel_width.unwrap_or(cell_width).max(1),
+            self.cell_pixel_height.unwrap_or(cell_height).max(1),
+        )
+    }
 }
 
 impl Plugin for RatatuiRenderPlugin {
     fn build(&self, app: &mut App) {
         if self.disabled {
-            app.init_resource::<RatatuiRenderContext>();
+            app.init_resource::<RatatuiRenderContext>().add_event::<RatatuiRenderReady>();
             return;
         }
 
+        assert!(
+            SUPPORTED_RENDER_TEXTURE_FORMATS.contains(&self.render_texture_format),
+            "render texture format {:?} of render \"{}\" cannot be printed, expected one of {:?}",
+            self.render_texture_format,
+            self.label,
+            SUPPORTED_RENDER_TEXTURE_FORMATS,
+        );
+
         if app
             .world
             .get_resource_mut::<RatatuiRenderContext>()
             .is_none()
         {
             app.init_resource::<RatatuiRenderContext>()
-                .add_systems(First, receive_rendered_images_system);
+                .init_resource::<ForceRedraw>()
+                .add_event::<RequestRedraw>()
+                .add_event::<RatatuiRenderReady>()
+                .add_systems(First, receive_rendered_images_system)
+                .add_systems(
+                    Last,
+                    force_redraw_system.run_if(resource_exists::<ForceRedraw>),
+                );
 
             let render_app = app.sub_app_mut(RenderApp);
 
@@ -137,9 +326,31 @@ impl Plugin for RatatuiRenderPlugin {
 
         app.add_systems(
             PreStartup,
-            initialize_context_system_generator(self.label.clone(), self.dimensions),
+            initialize_context_system_generator(
+                self.label.clone(),
+                self.dimensions(),
+                self.render_texture_format,
+                self.render_texture_usages,
+                self.capture_layers,
+                self.strategy.clone(),
+                self.color_config.clone(),
+            ),
         );
 
+        if self.autoresize {
+            app.add_event::<ResizeEvent>().add_systems(
+                Update,
+                autoresize_system_generator(self.label.clone(), self.cell_pixel_size()),
+            );
+        }
+
+        if let Some(frames) = self.snapshot_every {
+            app.add_systems(
+                Last,
+                snapshot_every_system_generator(self.label.clone(), frames),
+            );
+        }
+
         if self.print_full_terminal {
             app.add_systems(
                 Update,
@@ -180,23 +391,170 @@ impl RatatuiRenderContext {
     /// `RatatuiRenderPlugin` was instantiated.
     pub fn widget(&self, id: &str) -> Option<RatatuiRenderWidget> {
         let pipe = self.get(id)?;
-        Some(RatatuiRenderWidget::new(&pipe.image))
+        Some(
+            RatatuiRenderWidget::new(&pipe.image)
+                .strategy(&pipe.strategy)
+                .color_config(&pipe.color_config),
+        )
+    }
+
+    /// Whether the first render for the provided id has been received, so that its widget prints
+    /// something other than a blank image. Always false when the plugin is disabled.
+    ///
+    /// Can be used as a run condition, for example
+    /// `.run_if(|ratatui_render: Res<RatatuiRenderContext>| ratatui_render.is_ready("main"))`.
+    ///
+    /// * `id` - Unique descriptive identifier, must match the id provided when the corresponding
+    /// `RatatuiRenderPlugin` was instantiated.
+    pub fn is_ready(&self, id: &str) -> bool {
+        self.get(id).is_some_and(|pipe| pipe.is_ready())
+    }
+
+    /// Time elapsed since the render currently shown for the provided id was copied out of the
+    /// GPU, or None if no render has been received yet.
+    ///
+    /// * `id` - Unique descriptive identifier, must match the id provided when the corresponding
+    /// `RatatuiRenderPlugin` was instantiated.
+    pub fn frame_age(&self, id: &str) -> Option<Duration> {
+        self.get(id)?.frame_age()
+    }
+
+    /// Index of the render currently shown for the provided id, counting every render copied out
+    /// of the GPU for it, or None if no render has been received yet. Changes whenever a new
+    /// render is received.
+    ///
+    /// * `id` - Unique descriptive identifier, must match the id provided when the corresponding
+    /// `RatatuiRenderPlugin` was instantiated.
+    pub fn frame_index(&self, id: &str) -> Option<u64> {
+        self.get(id)?.frame_index()
     }
 }
 
+/// Marker resource that makes bevy redraw every frame, even when nothing in the scene has changed,
+/// so that the terminal always receives a fresh render.
+///
+/// Inserted automatically by `RatatuiRenderPlugin`. Remove it if your app only needs to render in
+/// response to changes (for example with a reactive `WinitSettings` update mode).
+#[derive(Resource, Default)]
+pub struct ForceRedraw;
+
+/// Computes render texture dimensions, (width, height), that fill an area of the terminal with
+/// square pixels, for sizing a render with `RatatuiRenderPlugin::new`.
+///
+/// * `width_cells`, `height_cells` - size of the area in terminal character cells.
+///
+/// * `cell_aspect` - height of a character cell divided by its width, usually around 2. Clamped
+/// to `0.25..=8.`, with NaN treated as 2.
+///
+/// * `use_half_block` - whether the render is printed with halfblocks, which print two pixels
+/// down each character cell instead of one.
+pub fn terminal_pixel_size(
+    width_cells: u16,
+    height_cells: u16,
+    cell_aspect: f32,
+    use_half_block: bool,
+) -> (u32, u32) {
+    let pixels_per_cell = if use_half_block { 2 } else { 1 };
+    let cell_aspect = if cell_aspect.is_nan() {
+        2.
+    } else {
+        cell_aspect.clamp(0.25, 8.)
+    };
+
+    let height = height_cells as u32 * pixels_per_cell;
+    let width = (width_cells as f32 * pixels_per_cell as f32 / cell_aspect)
+        .round()
+        .max(1.) as u32;
+
+    (width, height.max(1))
+}
+
+/// Sent once for each render, when its first frame is received from the GPU.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct RatatuiRenderReady {
+    /// Id of the render, as provided when the corresponding `RatatuiRenderPlugin` was instantiated.
+    pub id: String,
+}
+
 /// Creates a headless render pipe and adds it to the RatatuiRenderContext resource.
 fn initialize_context_system_generator(
     label: String,
     dimensions: (u32, u32),
+    format: TextureFormat,
+    usages: TextureUsages,
+    capture_layers: Option<RenderLayers>,
+    strategy: RatatuiRenderStrategy,
+    color_config: ColorConfig,
 ) -> impl FnMut(Commands, ResMut<Assets<Image>>, Res<RenderDevice>, ResMut<RatatuiRenderContext>) {
     move |mut commands, mut images, render_device, mut context| {
-        context.insert(
-            label.clone(),
-            HeadlessRenderPipe::new(&mut commands, &mut images, &render_device, dimensions),
+        let mut render_pipe = HeadlessRenderPipe::new(
+            &mut commands,
+            &mut images,
+            &render_device,
+            dimensions,
+            format,
+            usages,
+            capture_layers,
         );
+        render_pipe.strategy = strategy.clone();
+        render_pipe.color_config = color_config.clone();
+
+        context.insert(label.clone(), render_pipe);
+    }
+}
+
+/// Schedules the copier for the provided id on one frame out of every `frames`.
+fn snapshot_every_system_generator(
+    id: String,
+    frames: u32,
+) -> impl FnMut(Res<RatatuiRenderContext>) {
+    let mut frame_count = 0;
+
+    move |ratatui_render| {
+        if let Some(render_pipe) = ratatui_render.get(&id) {
+            render_pipe.set_scheduled(frame_count == 0);
+            frame_count = (frame_count + 1) % frames;
+        }
+    }
+}
+
+/// Resizes the render pipe for the provided id to fill the terminal after it is resized.
+#[allow(clippy::type_complexity)]
+fn autoresize_system_generator(
+    id: String,
+    cell_pixel_size: (u32, u32),
+) -> impl FnMut(
+    EventReader<ResizeEvent>,
+    ResMut<Assets<Image>>,
+    Res<RenderDevice>,
+    ResMut<RatatuiRenderContext>,
+    Query<&mut ImageCopier>,
+) {
+    move |mut resized, mut images, render_device, mut ratatui_render, mut image_copiers| {
+        let Some(ResizeEvent(size)) = resized.read().last() else {
+            return;
+        };
+
+        if let Some(render_pipe) = ratatui_render.get_mut(&id) {
+            let (cell_width, cell_height) = cell_pixel_size;
+            render_pipe.resize(
+                (
+                    size.width.max(1) as u32 * cell_width,
+                    size.height.max(1) as u32 * cell_height,
+                ),
+                &mut images,
+                &render_device,
+                &mut image_copiers,
+            );
+        }
     }
 }
 
+/// Requests a redraw each frame while the `ForceRedraw` resource is present.
+fn force_redraw_system(mut redraw: EventWriter<RequestRedraw>) {
+    redraw.send(RequestRedraw);
+}
+
 /// Draws the widget for the provided id in the full terminal, each frame.
 fn print_full_terminal_system(
     id: String,
@@ -211,3 +569,61 @@ fn print_full_terminal_system(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_plugin_registers_events() {
+        let mut app = App::new();
+        app.add_plugins(RatatuiRenderPlugin::new("main", (1, 1)).disable())
+            .add_systems(Update, |mut ready: EventReader<RatatuiRenderReady>| {
+                assert_eq!(ready.read().count(), 0);
+            });
+
+        app.update();
+    }
+
+    #[test]
+    fn terminal_pixel_size_fills_cells_with_square_pixels() {
+        assert_eq!(terminal_pixel_size(80, 24, 2., true), (80, 48));
+        assert_eq!(terminal_pixel_size(80, 24, 2., false), (40, 24));
+        assert_eq!(terminal_pixel_size(81, 24, 2., false), (41, 24));
+    }
+
+    #[test]
+    fn terminal_pixel_size_is_at_least_one_pixel() {
+        assert_eq!(terminal_pixel_size(0, 0, 2., true), (1, 1));
+    }
+
+    #[test]
+    fn terminal_pixel_size_clamps_the_cell_aspect() {
+        assert_eq!(terminal_pixel_size(80, 24, 0., true), (640, 48));
+        assert_eq!(terminal_pixel_size(80, 24, -1., true), (640, 48));
+        assert_eq!(terminal_pixel_size(80, 24, f32::INFINITY, true), (20, 48));
+        assert_eq!(terminal_pixel_size(80, 24, f32::NAN, true), (80, 48));
+    }
+
+    #[test]
+    fn snapshot_every_schedules_every_nth_frame() {
+        const FRAMES: u32 = 3;
+
+        let (render_pipe, _sender) = HeadlessRenderPipe::detached();
+        let mut ratatui_render = RatatuiRenderContext::default();
+        ratatui_render.insert("main".into(), render_pipe);
+
+        let mut world = World::new();
+        world.insert_resource(ratatui_render);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(snapshot_every_system_generator("main".into(), FRAMES));
+
+        for frame in 0..=2 * FRAMES {
+            schedule.run(&mut world);
+
+            let scheduled = world.resource::<RatatuiRenderContext>()["main"].scheduled();
+            assert_eq!(scheduled, frame % FRAMES == 0, "frame {frame}");
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    fs::File,
+    io::BufWriter,
+    iter, mem,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageResult, RgbaImage,
+};
+
+use crate::{
+    headless::{image_to_dynamic, receive_rendered_images_system},
+    RatatuiRenderContext,
+};
+
+/// Records the render of the provided id into an animated GIF, through the `GifRecorder` resource.
+///
+/// Frames are kept in memory until `GifRecorder::save(path)` is called, or until the app exits if
+/// `output_path` is set. `max_frames` caps how many frames are kept, after which recording stops.
+///
+/// # example:
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ratatui::RatatuiPlugins;
+/// # use bevy_ratatui_render::{GifRecordingPlugin, RatatuiRenderPlugin};
+/// #
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     RatatuiPlugins::default(),
+///     RatatuiRenderPlugin::new("main", (256, 256)),
+///     GifRecordingPlugin {
+///         id: "main".into(),
+///         record_every_n_frames: 2,
+///         max_frames: 300,
+///         output_path: Some("recording.gif".into()),
+///     },
+/// ));
+/// ```
+pub struct GifRecordingPlugin {
+    /// Id of the render to record, as passed to `RatatuiRenderPlugin::new`.
+    pub id: String,
+    /// Number of renders received per recorded frame. `1` records every new render.
+    pub record_every_n_frames: u32,
+    /// Maximum number of frames kept in memory.
+    pub max_frames: usize,
+    /// Path the recording is saved to when the app exits, if any.
+    pub output_path: Option<PathBuf>,
+}
+
+impl Plugin for GifRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GifRecorder {
+            id: self.id.clone(),
+            record_every_n_frames: self.record_every_n_frames.max(1),
+            max_frames: self.max_frames,
+            output_path: self.output_path.clone(),
+            frame_count: 0,
+            last_frame_index: None,
+            frames: Vec::new(),
+        })
+        .add_systems(
+            First,
+            record_frame_system.after(receive_rendered_images_system),
+        );
+    }
+}
+
+/// Resource holding the frames recorded by `GifRecordingPlugin`.
+#[derive(Resource)]
+pub struct GifRecorder {
+    id: String,
+    record_every_n_frames: u32,
+    max_frames: usize,
+    output_path: Option<PathBuf>,
+    frame_count: u32,
+    last_frame_index: Option<u64>,
+    frames: Vec<(RgbaImage, Duration)>,
+}
+
+impl GifRecorder {
+    /// Number of frames recorded since the last save.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames have been recorded since the last save.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes the recorded frames into an animated GIF at `path`, looping forever. The recorded
+    /// frames are cleared, and recording resumes from an empty recording afterwards.
+    pub fn save(&mut self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let frames = mem::take(&mut self.frames);
+
+        // Each frame is shown until the next one was recorded. The last frame has nothing to
+        // measure against, so it is shown for the average delay of the recording.
+        let average = match (frames.first(), frames.last()) {
+            (Some((_, first)), Some((_, last))) if frames.len() > 1 => {
+                (*last - *first) / (frames.len() - 1) as u32
+            }
+            _ => Duration::ZERO,
+        };
+        let delays = frames
+            .windows(2)
+            .map(|pair| pair[1].1 - pair[0].1)
+            .chain(iter::once(average))
+            .collect::<Vec<_>>();
+
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+        encoder.encode_frames(frames.into_iter().zip(delays).map(|((image, _), delay)| {
+            Frame::from_parts(image, 0, 0, Delay::from_saturating_duration(delay))
+        }))
+    }
+}
+
+impl Drop for GifRecorder {
+    fn drop(&mut self) {
+        if self.frames.is_empty() {
+            return;
+        }
+
+        if let Some(path) = self.output_path.take() {
+            if let Err(e) = self.save(&path) {
+                error!("failed to save recording to {}: {e}", path.display());
+            }
+        }
+    }
+}
+
+/// Copies the render into the recorder once out of every `record_every_n_frames` new renders.
+/// Frames where no new render was received are skipped, so the same render is never recorded twice.
+fn record_frame_system(
+    time: Res<Time>,
+    mut recorder: ResMut<GifRecorder>,
+    ratatui_render: Res<RatatuiRenderContext>,
+) {
+    let Some(render_pipe) = ratatui_render.get(&recorder.id) else {
+        return;
+    };
+
+    let Some(frame_index) = render_pipe.frame_index() else {
+        return;
+    };
+    if recorder.last_frame_index.replace(frame_index) == Some(frame_index) {
+        return;
+    }
+
+    let frame_count = recorder.frame_count;
+    recorder.frame_count = (frame_count + 1) % recorder.record_every_n_frames;
+
+    if frame_count != 0 || recorder.frames.len() >= recorder.max_frames {
+        return;
+    }
+
+    if let Ok(image) = image_to_dynamic(&render_pipe.image) {
+        recorder.frames.push((image.into_rgba8(), time.elapsed()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::BufReader};
+
+    use image::{codecs::gif::GifDecoder, AnimationDecoder, Rgba};
+
+    use super::*;
+
+    #[test]
+    fn save_encodes_every_frame() {
+        let mut recorder = GifRecorder {
+            id: "main".into(),
+            record_every_n_frames: 1,
+            max_frames: 4,
+            output_path: None,
+            frame_count: 0,
+            last_frame_index: None,
+            frames: (0..4)
+                .map(|index| {
+                    let image = RgbaImage::from_pixel(3, 2, Rgba([index * 60, 0, 0, 255]));
+                    (image, Duration::from_millis(100 * index as u64))
+                })
+                .collect(),
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "bevy_ratatui_render_recording_{}.gif",
+            std::process::id()
+        ));
+        recorder.save(&path).unwrap();
+        assert!(recorder.is_empty());
+
+        let decoder = GifDecoder::new(BufReader::new(File::open(&path).unwrap())).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 4);
+        for frame in frames {
+            assert_eq!(frame.buffer().dimensions(), (3, 2));
+            assert_eq!(Duration::from(frame.delay()), Duration::from_millis(100));
+        }
+    }
+}
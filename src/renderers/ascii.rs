@@ -0,0 +1,90 @@
+use image::DynamicImage;
+
+/// Configuration for `RatatuiRenderStrategy::Ascii`.
+///
+/// Each character cell covers a 1x2 block of pixels of the render, printed as the character of
+/// `ramp` closest to their average luminance.
+#[derive(Clone, Debug)]
+pub struct AsciiConfig {
+    /// Characters ordered from darkest to brightest.
+    pub ramp: String,
+    /// Flips the ramp, so that the first character is used for the brightest pixels. Useful for
+    /// terminals with a light background.
+    pub invert: bool,
+}
+
+impl Default for AsciiConfig {
+    fn default() -> Self {
+        Self {
+            ramp: " .:-=+*#%@".into(),
+            invert: false,
+        }
+    }
+}
+
+/// Converts an image into rows of ascii characters, one character for each pixel, using the BT.709
+/// luminance of the pixel to pick a character from the ramp. Returns blank rows if the ramp is
+/// empty.
+pub fn image_to_ascii(img: &DynamicImage, cfg: &AsciiConfig) -> Vec<String> {
+    let rgb = img.to_rgb8();
+    let ramp = cfg.ramp.chars().collect::<Vec<_>>();
+    let last = ramp.len().saturating_sub(1) as f32;
+
+    rgb.rows()
+        .map(|row| {
+            row.map(|pixel| {
+                let [r, g, b] = pixel.0;
+                let luminance = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.;
+                let luminance = if cfg.invert {
+                    1. - luminance
+                } else {
+                    luminance
+                };
+
+                ramp.get((luminance * last).round() as usize)
+                    .copied()
+                    .unwrap_or(' ')
+            })
+            .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use super::*;
+
+    /// One pixel for each character of the default ramp, from black to white.
+    fn gradient() -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(10, 1, |x, _| {
+            Rgb([(255. * x as f32 / 9.).round() as u8; 3])
+        }))
+    }
+
+    #[test]
+    fn gradient_round_trips_through_the_ramp() {
+        let config = AsciiConfig::default();
+        assert_eq!(image_to_ascii(&gradient(), &config), vec![config.ramp]);
+    }
+
+    #[test]
+    fn invert_reverses_the_ramp() {
+        let config = AsciiConfig {
+            invert: true,
+            ..Default::default()
+        };
+        let reversed = config.ramp.chars().rev().collect::<String>();
+        assert_eq!(image_to_ascii(&gradient(), &config), vec![reversed]);
+    }
+
+    #[test]
+    fn empty_ramp_prints_blanks() {
+        let config = AsciiConfig {
+            ramp: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(image_to_ascii(&gradient(), &config), vec![" ".repeat(10)]);
+    }
+}
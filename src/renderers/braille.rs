@@ -0,0 +1,100 @@
+use image::DynamicImage;
+use ratatui::style::Color;
+
+/// Configuration for `RatatuiRenderStrategy::Braille`.
+///
+/// Each character cell covers a 2x4 block of pixels, raising the braille dot of every pixel whose
+/// luminance is above `threshold`.
+#[derive(Clone, Debug)]
+pub struct BrailleConfig {
+    /// Luminance from 0 to 1 above which a pixel's dot is raised.
+    pub threshold: f32,
+    /// Color of the raised dots.
+    pub foreground: Color,
+    /// Color behind the dots.
+    pub background: Color,
+}
+
+impl Default for BrailleConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            foreground: Color::White,
+            background: Color::Black,
+        }
+    }
+}
+
+/// Codepoint of the blank braille pattern, which each raised dot adds its bit to.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Bit of each dot in a braille pattern, indexed by its `[x][y]` position in the 2x4 grid.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Converts an image into rows of braille characters, one character for each 2x4 block of pixels.
+/// Blocks hanging over the right or bottom edge of the image treat the missing pixels as unlit.
+pub fn image_to_braille(img: &DynamicImage, cfg: &BrailleConfig) -> Vec<Vec<char>> {
+    let luma = img.to_luma8();
+    let (width, height) = luma.dimensions();
+
+    (0..height.div_ceil(4))
+        .map(|row| {
+            (0..width.div_ceil(2))
+                .map(|column| {
+                    let mut pattern = 0;
+                    for (dx, dots) in BRAILLE_DOTS.iter().enumerate() {
+                        for (dy, dot) in dots.iter().enumerate() {
+                            let x = column * 2 + dx as u32;
+                            let y = row * 4 + dy as u32;
+                            if x < width
+                                && y < height
+                                && luma.get_pixel(x, y).0[0] as f32 / 255. > cfg.threshold
+                            {
+                                pattern |= dot;
+                            }
+                        }
+                    }
+                    char::from_u32(BRAILLE_BLANK + pattern).unwrap_or(' ')
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use super::*;
+
+    fn filled(width: u32, height: u32, value: u8) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb([value; 3])))
+    }
+
+    #[test]
+    fn white_block_raises_every_dot() {
+        let braille = image_to_braille(&filled(2, 4, 255), &BrailleConfig::default());
+        assert_eq!(braille, vec![vec!['⣿']]);
+    }
+
+    #[test]
+    fn black_block_is_blank() {
+        let braille = image_to_braille(&filled(2, 4, 0), &BrailleConfig::default());
+        assert_eq!(braille, vec![vec!['⠀']]);
+    }
+
+    #[test]
+    fn single_pixel_raises_its_dot() {
+        let mut image = RgbImage::new(2, 4);
+        image.put_pixel(1, 3, Rgb([255; 3]));
+
+        let braille = image_to_braille(&DynamicImage::ImageRgb8(image), &BrailleConfig::default());
+        assert_eq!(braille, vec![vec!['⢀']]);
+    }
+
+    #[test]
+    fn partial_blocks_treat_missing_pixels_as_unlit() {
+        let braille = image_to_braille(&filled(3, 5, 255), &BrailleConfig::default());
+        assert_eq!(braille, vec![vec!['⣿', '⡇'], vec!['⠉', '⠁']]);
+    }
+}
@@ -0,0 +1,379 @@
+use ratatui::style::Color;
+
+use super::DitherMode;
+
+/// Range of colors the terminal can display. Renders are quantized to the nearest displayable color
+/// before printing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit color, printed as is.
+    #[default]
+    TrueColor,
+
+    /// The xterm 256 color palette. Only the 6x6x6 color cube and the 24 step grayscale ramp are
+    /// used, as the first 16 colors vary between terminal themes.
+    Colors256,
+
+    /// The 16 basic ANSI colors, matched against the `Colors16Palette` of the `ColorConfig`, which
+    /// defaults to the standard VGA palette.
+    Colors16,
+
+    /// The 24 step grayscale ramp of the xterm 256 color palette, plus black and white.
+    Grayscale,
+}
+
+/// How the distance between two colors is measured when looking for the nearest color in a
+/// palette.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMetric {
+    /// Squared euclidean distance between rgb values.
+    #[default]
+    EuclideanRgb,
+
+    /// Squared euclidean distance between YUV values, with differences in luma weighted twice as
+    /// much as differences in chroma. Tends to preserve brightness better than `EuclideanRgb`.
+    WeightedYuv,
+}
+
+/// Rgb values the terminal displays for each of the 16 basic ANSI colors, to match renders
+/// against with `ColorDepth::Colors16`. Defaults to the standard VGA palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Colors16Palette([[u8; 3]; 16]);
+
+impl Default for Colors16Palette {
+    fn default() -> Self {
+        Self(ANSI_16_PALETTE)
+    }
+}
+
+impl Colors16Palette {
+    /// Creates a palette for a custom terminal theme, such as Solarized or Nord.
+    ///
+    /// * `dark` - black, red, green, yellow, blue, magenta, cyan and white.
+    ///
+    /// * `bright` - the bright variants of the same colors, in the same order.
+    pub fn from_rgb_pairs(dark: [(u8, u8, u8); 8], bright: [(u8, u8, u8); 8]) -> Self {
+        Self(std::array::from_fn(|index| {
+            let (r, g, b) = if index < 8 {
+                dark[index]
+            } else {
+                bright[index - 8]
+            };
+            [r, g, b]
+        }))
+    }
+}
+
+/// Configuration for how colors of the render are printed to the terminal.
+#[derive(Clone, Debug, Default)]
+pub struct ColorConfig {
+    pub depth: ColorDepth,
+    pub dither: DitherMode,
+    pub palette_16: Colors16Palette,
+    pub metric: ColorMetric,
+}
+
+impl ColorConfig {
+    /// The same configuration as `ColorConfig::default()`, for use where a constant is needed.
+    pub(crate) const DEFAULT: Self = Self {
+        depth: ColorDepth::TrueColor,
+        dither: DitherMode::None,
+        palette_16: Colors16Palette(ANSI_16_PALETTE),
+        metric: ColorMetric::EuclideanRgb,
+    };
+
+    /// Converts an rgb pixel into the nearest color displayable at the configured depth.
+    pub fn quantize(&self, rgb: [u8; 3]) -> Color {
+        self.quantize_rgb(rgb).0
+    }
+
+    /// Converts an rgb pixel into the nearest color displayable at the configured depth, along
+    /// with the rgb values it is displayed as.
+    pub(super) fn quantize_rgb(&self, rgb: [u8; 3]) -> (Color, [u8; 3]) {
+        match self.depth {
+            ColorDepth::TrueColor => (Color::Rgb(rgb[0], rgb[1], rgb[2]), rgb),
+            ColorDepth::Colors256 => {
+                let index = xterm_256_index(rgb, self.metric);
+                (Color::Indexed(16 + index as u8), XTERM_256_PALETTE[index])
+            }
+            ColorDepth::Colors16 => {
+                let index = nearest(&self.palette_16.0, rgb, self.metric);
+                (ANSI_16_COLORS[index], self.palette_16.0[index])
+            }
+            ColorDepth::Grayscale => {
+                let index = grayscale_index(rgb);
+                (Color::Indexed(index), XTERM_256_PALETTE[(index - 16) as usize])
+            }
+        }
+    }
+
+    /// Converts `Color::Rgb` into the nearest color displayable at the configured depth. Named and
+    /// indexed colors are left to the terminal.
+    pub fn convert(&self, color: Color) -> Color {
+        match color {
+            Color::Rgb(r, g, b) => self.quantize([r, g, b]),
+            color => color,
+        }
+    }
+}
+
+impl ColorDepth {
+    /// Converts an rgb pixel into the nearest color displayable at this depth, using the default
+    /// palette and metric of `ColorConfig`.
+    pub fn quantize(&self, rgb: [u8; 3]) -> Color {
+        self.config().quantize(rgb)
+    }
+
+    /// Converts `Color::Rgb` into the nearest color displayable at this depth, using the default
+    /// palette and metric of `ColorConfig`. Named and indexed colors are left to the terminal.
+    pub fn convert(&self, color: Color) -> Color {
+        self.config().convert(color)
+    }
+
+    fn config(&self) -> ColorConfig {
+        ColorConfig {
+            depth: *self,
+            ..Default::default()
+        }
+    }
+}
+
+/// Channel levels of the xterm 6x6x6 color cube.
+const XTERM_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// xterm colors 16 to 255: the 6x6x6 color cube followed by the 24 step grayscale ramp.
+const XTERM_256_PALETTE: [[u8; 3]; 240] = xterm_256_palette();
+
+const fn xterm_256_palette() -> [[u8; 3]; 240] {
+    let mut palette = [[0; 3]; 240];
+
+    let mut index = 0;
+    while index < 216 {
+        palette[index] = [
+            XTERM_CUBE_LEVELS[index / 36],
+            XTERM_CUBE_LEVELS[index / 6 % 6],
+            XTERM_CUBE_LEVELS[index % 6],
+        ];
+        index += 1;
+    }
+    while index < 240 {
+        palette[index] = [8 + 10 * (index - 216) as u8; 3];
+        index += 1;
+    }
+
+    palette
+}
+
+/// Index into `XTERM_256_PALETTE` of the color nearest to `rgb` by the provided metric.
+///
+/// Rather than scanning all 240 colors, this takes the nearest cube level of each channel and the
+/// grayscale step nearest to the channel average, and compares only those two. That finds the
+/// nearest color exactly by `ColorMetric::EuclideanRgb`, and closely by `ColorMetric::WeightedYuv`.
+fn xterm_256_index(rgb: [u8; 3], metric: ColorMetric) -> usize {
+    let [r, g, b] = rgb.map(nearest_cube_level);
+    let cube = 36 * r + 6 * g + b;
+
+    let average = rgb.iter().map(|&channel| channel as f32).sum::<f32>() / 3.;
+    let gray = 216 + ((average - 8.) / 10.).round().clamp(0., 23.) as usize;
+
+    let distance = |index: usize| metric.distance(XTERM_256_PALETTE[index], rgb);
+    if distance(gray) < distance(cube) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// Index of the level in `XTERM_CUBE_LEVELS` nearest to a channel value, taking the darker level
+/// on ties. The levels after the first two are spaced 40 apart.
+fn nearest_cube_level(value: u8) -> usize {
+    match value {
+        0..=47 => 0,
+        48..=115 => 1,
+        value => (value as usize - 36) / 40,
+    }
+}
+
+/// The 16 basic ANSI colors, in the order of `ANSI_16_PALETTE`.
+const ANSI_16_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Standard VGA values of the 16 basic ANSI colors.
+const ANSI_16_PALETTE: [[u8; 3]; 16] = [
+    [0x00, 0x00, 0x00],
+    [0xaa, 0x00, 0x00],
+    [0x00, 0xaa, 0x00],
+    [0xaa, 0x55, 0x00],
+    [0x00, 0x00, 0xaa],
+    [0xaa, 0x00, 0xaa],
+    [0x00, 0xaa, 0xaa],
+    [0xaa, 0xaa, 0xaa],
+    [0x55, 0x55, 0x55],
+    [0xff, 0x55, 0x55],
+    [0x55, 0xff, 0x55],
+    [0xff, 0xff, 0x55],
+    [0x55, 0x55, 0xff],
+    [0xff, 0x55, 0xff],
+    [0x55, 0xff, 0xff],
+    [0xff, 0xff, 0xff],
+];
+
+/// Index of the palette color nearest to `rgb` by the provided metric.
+pub(super) fn nearest(palette: &[[u8; 3]], rgb: [u8; 3], metric: ColorMetric) -> usize {
+    palette
+        .iter()
+        .map(|&color| metric.distance(color, rgb))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(index, _)| index)
+}
+
+impl ColorMetric {
+    fn distance(&self, a: [u8; 3], b: [u8; 3]) -> f32 {
+        match self {
+            Self::EuclideanRgb => a
+                .iter()
+                .zip(b)
+                .map(|(&a, b)| (a as f32 - b as f32).powi(2))
+                .sum(),
+            Self::WeightedYuv => {
+                let [ay, au, av] = yuv(a);
+                let [by, bu, bv] = yuv(b);
+                2. * (ay - by).powi(2) + (au - bu).powi(2) + (av - bv).powi(2)
+            }
+        }
+    }
+}
+
+/// BT.601 YUV values of an rgb color.
+fn yuv([r, g, b]: [u8; 3]) -> [f32; 3] {
+    let (r, g, b) = (r as f32, g as f32, b as f32);
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    [y, 0.492 * (b - y), 0.877 * (r - y)]
+}
+
+fn grayscale_index([r, g, b]: [u8; 3]) -> u8 {
+    let luminance = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+
+    if luminance < 4. {
+        16
+    } else if luminance > 246.5 {
+        231
+    } else {
+        232 + ((luminance - 8.) / 10.).round().clamp(0., 23.) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pure_red_is_xterm_196() {
+        let config = ColorConfig {
+            depth: ColorDepth::Colors256,
+            ..Default::default()
+        };
+        assert_eq!(config.quantize([255, 0, 0]), Color::Indexed(196));
+    }
+
+    #[test]
+    fn mid_gray_uses_the_grayscale_ramp() {
+        let config = ColorConfig {
+            depth: ColorDepth::Colors256,
+            ..Default::default()
+        };
+        assert_eq!(config.quantize([128, 128, 128]), Color::Indexed(244));
+    }
+
+    #[test]
+    fn pure_red_is_ansi_red_with_the_default_palette() {
+        let config = ColorConfig {
+            depth: ColorDepth::Colors16,
+            ..Default::default()
+        };
+        assert_eq!(config.quantize([0xff, 0x00, 0x00]), Color::Red);
+    }
+
+    #[test]
+    fn from_rgb_pairs_orders_dark_then_bright() {
+        let dark = std::array::from_fn(|index| (1 + index as u8, 0, 0));
+        let bright = std::array::from_fn(|index| (0, 1 + index as u8, 0));
+        let Colors16Palette(palette) = Colors16Palette::from_rgb_pairs(dark, bright);
+
+        assert_eq!(palette[0], [1, 0, 0]);
+        assert_eq!(palette[7], [8, 0, 0]);
+        assert_eq!(palette[8], [0, 1, 0]);
+        assert_eq!(palette[15], [0, 8, 0]);
+    }
+
+    #[test]
+    fn custom_palette_maps_to_ansi_colors_by_position() {
+        let config = ColorConfig {
+            depth: ColorDepth::Colors16,
+            palette_16: Colors16Palette::from_rgb_pairs(
+                [(0x00, 0x2b, 0x36); 8],
+                std::array::from_fn(|index| (0x10 * index as u8, 0x80, 0x80)),
+            ),
+            ..Default::default()
+        };
+        assert_eq!(config.quantize([0x10, 0x80, 0x80]), Color::LightRed);
+        assert_eq!(config.quantize([0x00, 0x2b, 0x36]), Color::Black);
+    }
+
+    #[test]
+    fn weighted_yuv_weights_luma_twice_as_much_as_chroma() {
+        let distance = ColorMetric::WeightedYuv.distance([0; 3], [10; 3]);
+        assert!((distance - 200.).abs() < 1e-3, "{distance}");
+        assert_eq!(ColorMetric::EuclideanRgb.distance([0; 3], [10; 3]), 300.);
+    }
+
+    #[test]
+    fn weighted_yuv_favors_matching_brightness() {
+        let rgb = [0x00, 0xff, 0x00];
+        let palette = &ANSI_16_PALETTE;
+        assert_eq!(
+            ANSI_16_COLORS[nearest(palette, rgb, ColorMetric::EuclideanRgb)],
+            Color::Green
+        );
+        assert_eq!(
+            ANSI_16_COLORS[nearest(palette, rgb, ColorMetric::WeightedYuv)],
+            Color::LightGreen
+        );
+    }
+
+    #[test]
+    fn xterm_256_index_matches_a_full_scan() {
+        let metric = ColorMetric::EuclideanRgb;
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(51) {
+                    let rgb = [r, g, b];
+                    let scanned = nearest(&XTERM_256_PALETTE, rgb, metric);
+                    let computed = xterm_256_index(rgb, metric);
+                    assert_eq!(
+                        metric.distance(XTERM_256_PALETTE[computed], rgb),
+                        metric.distance(XTERM_256_PALETTE[scanned], rgb),
+                        "{rgb:?}",
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::array;
+
+use image::DynamicImage;
+
+use super::{color::nearest, ColorConfig, ColorDepth, ColorMetric};
+
+/// Dithering applied to a render before it is quantized to a limited `ColorDepth`, trading flat
+/// bands of color for a fine pattern that reads as a smoother gradient. Has no effect at
+/// `ColorDepth::TrueColor`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherMode {
+    /// Colors are quantized as is.
+    #[default]
+    None,
+
+    /// Ordered dithering with a 2x2 Bayer matrix.
+    Bayer2x2,
+
+    /// Ordered dithering with a 4x4 Bayer matrix.
+    Bayer4x4,
+
+    /// Ordered dithering with an 8x8 Bayer matrix.
+    Bayer8x8,
+
+    /// Floyd-Steinberg error diffusion, which avoids the regular pattern of the Bayer matrices
+    /// and suits photographic content better, but is slower.
+    FloydSteinberg,
+}
+
+impl DitherMode {
+    /// Width and height of the threshold matrix, if this mode uses one.
+    fn matrix_size(&self) -> Option<u32> {
+        match self {
+            Self::None | Self::FloydSteinberg => None,
+            Self::Bayer2x2 => Some(2),
+            Self::Bayer4x4 => Some(4),
+            Self::Bayer8x8 => Some(8),
+        }
+    }
+}
+
+impl ColorDepth {
+    /// Approximate distance between neighboring levels of a channel at this depth, which is how
+    /// far dithering needs to nudge a pixel to reach the next color.
+    fn dither_spread(&self) -> f32 {
+        match self {
+            Self::TrueColor => 0.,
+            Self::Colors256 => 40.,
+            Self::Colors16 => 85.,
+            Self::Grayscale => 10.,
+        }
+    }
+}
+
+/// The 2x2 Bayer matrix, from which the larger matrices are built.
+const BAYER_2X2: [[u32; 2]; 2] = [[0, 2], [3, 1]];
+
+/// Applies ordered dithering to the image in place, offsetting each pixel by the threshold matrix
+/// of `mode`, scaled to the distance between the colors available at `depth`.
+pub fn apply_dither(img: &mut DynamicImage, mode: DitherMode, depth: ColorDepth) {
+    dither_with_config(
+        img,
+        &ColorConfig {
+            depth,
+            dither: mode,
+            ..Default::default()
+        },
+    );
+}
+
+/// Applies the dithering of `color_config` to the image in place, diffusing error against its
+/// configured palette and metric.
+pub(crate) fn dither_with_config(img: &mut DynamicImage, color_config: &ColorConfig) {
+    let ColorConfig {
+        depth,
+        dither: mode,
+        ..
+    } = *color_config;
+
+    if mode == DitherMode::FloydSteinberg {
+        if depth != ColorDepth::TrueColor {
+            floyd_steinberg(img, |rgb| color_config.quantize_rgb(rgb).1);
+        }
+        return;
+    }
+
+    let Some(size) = mode.matrix_size() else {
+        return;
+    };
+
+    let spread = depth.dither_spread();
+    if spread == 0. {
+        return;
+    }
+
+    let mut rgba = img.to_rgba8();
+    for (x, y, pixel) in rgba.enumerate_pixels_mut() {
+        let offset = bayer_threshold(x % size, y % size, size) * spread;
+        for channel in &mut pixel.0[..3] {
+            *channel = (*channel as f32 + offset).round().clamp(0., 255.) as u8;
+        }
+    }
+
+    *img = DynamicImage::ImageRgba8(rgba);
+}
+
+/// Offset to each neighbor of a pixel, and the share of the pixel's quantization error it receives.
+const FLOYD_STEINBERG_WEIGHTS: [(i32, u32, f32); 4] = [
+    (1, 0, 7. / 16.),
+    (-1, 1, 3. / 16.),
+    (0, 1, 5. / 16.),
+    (1, 1, 1. / 16.),
+];
+
+/// Replaces each pixel of the image with the nearest palette color, diffusing the difference onto
+/// the neighboring pixels that have not been visited yet with Floyd-Steinberg weights. Does nothing
+/// if the palette is empty.
+///
+/// Each pixel depends on the error carried over from the pixels before it, so the image is
+/// processed one pixel at a time in order and cannot trivially be split across threads.
+pub fn apply_floyd_steinberg(img: &mut DynamicImage, palette: &[[u8; 3]]) {
+    if palette.is_empty() {
+        return;
+    }
+
+    floyd_steinberg(img, |rgb| palette[nearest(palette, rgb, ColorMetric::EuclideanRgb)]);
+}
+
+/// Floyd-Steinberg error diffusion, replacing each pixel with the rgb values `quantize` maps it to.
+fn floyd_steinberg(img: &mut DynamicImage, quantize: impl Fn([u8; 3]) -> [u8; 3]) {
+    let mut rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let mut errors = vec![[0f32; 3]; width as usize * height as usize];
+
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = rgba.get_pixel_mut(x, y);
+            let carried = errors[(y * width + x) as usize];
+
+            let wanted: [f32; 3] = array::from_fn(|c| pixel.0[c] as f32 + carried[c]);
+            let color = quantize(array::from_fn(|c| wanted[c].round().clamp(0., 255.) as u8));
+            pixel.0[..3].copy_from_slice(&color);
+
+            let error: [f32; 3] = array::from_fn(|c| wanted[c] - color[c] as f32);
+            for (dx, dy, weight) in FLOYD_STEINBERG_WEIGHTS {
+                let Some(nx) = x.checked_add_signed(dx).filter(|&nx| nx < width) else {
+                    continue;
+                };
+                let ny = y + dy;
+                if ny >= height {
+                    continue;
+                }
+
+                let neighbor = &mut errors[(ny * width + nx) as usize];
+                for (channel, error) in neighbor.iter_mut().zip(error) {
+                    *channel += error * weight;
+                }
+            }
+        }
+    }
+
+    *img = DynamicImage::ImageRgba8(rgba);
+}
+
+/// Threshold of the `size`x`size` Bayer matrix at (x, y), centered around zero in `-0.5..0.5`.
+///
+/// Each power of two level of the position picks an entry of the 2x2 matrix, with the finest
+/// level weighted most, which is equivalent to building the matrix recursively.
+fn bayer_threshold(x: u32, y: u32, size: u32) -> f32 {
+    let levels = size.trailing_zeros();
+
+    let value = (0..levels).fold(0, |value, level| {
+        let entry = BAYER_2X2[((y >> level) & 1) as usize][((x >> level) & 1) as usize];
+        value + entry * 4u32.pow(levels - 1 - level)
+    });
+
+    (value as f32 + 0.5) / (size * size) as f32 - 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn bayer_4x4_matches_the_standard_matrix() {
+        const BAYER_4X4: [[u32; 4]; 4] = [
+            [0, 8, 2, 10],
+            [12, 4, 14, 6],
+            [3, 11, 1, 9],
+            [15, 7, 13, 5],
+        ];
+
+        for (y, row) in BAYER_4X4.iter().enumerate() {
+            for (x, &expected) in row.iter().enumerate() {
+                let threshold = bayer_threshold(x as u32, y as u32, 4);
+                assert_eq!(((threshold + 0.5) * 16. - 0.5).round() as u32, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn bayer_dithers_a_gradient() {
+        let gradient = RgbaImage::from_fn(4, 4, |x, _| Rgba([40 + 50 * x as u8; 4]));
+        let mut img = DynamicImage::ImageRgba8(gradient);
+
+        apply_dither(&mut img, DitherMode::Bayer4x4, ColorDepth::Colors16);
+
+        let dithered = img.to_rgba8();
+        let expected = [
+            [0, 93, 111, 203],
+            [64, 71, 175, 182],
+            [16, 109, 105, 198],
+            [80, 87, 169, 177],
+        ];
+        for (x, y, pixel) in dithered.enumerate_pixels() {
+            let value = expected[y as usize][x as usize];
+            assert_eq!(pixel.0, [value, value, value, 40 + 50 * x as u8], "({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn floyd_steinberg_only_uses_palette_colors() {
+        let palette = [[0; 3], [255; 3]];
+        let mut img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(4, 4, Rgba([128; 4])));
+
+        apply_floyd_steinberg(&mut img, &palette);
+
+        let dithered = img.to_rgba8();
+        let white = dithered.pixels().filter(|pixel| pixel.0[0] == 255).count();
+        assert!(dithered
+            .pixels()
+            .all(|pixel| palette.contains(&[pixel.0[0], pixel.0[1], pixel.0[2]])));
+        assert_eq!(white, 8);
+    }
+}
@@ -0,0 +1,9 @@
+mod ascii;
+mod braille;
+mod color;
+pub(crate) mod dither;
+
+pub use ascii::{image_to_ascii, AsciiConfig};
+pub use braille::{image_to_braille, BrailleConfig};
+pub use color::{ColorConfig, ColorDepth, ColorMetric, Colors16Palette};
+pub use dither::{apply_dither, apply_floyd_steinberg, DitherMode};
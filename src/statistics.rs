@@ -0,0 +1,191 @@
+use std::fmt;
+
+use bevy::{prelude::*, utils::hashbrown::HashMap};
+
+use crate::{
+    headless::{image_to_dynamic, receive_rendered_images_system},
+    RatatuiRenderContext,
+};
+
+/// Logs the min/max/mean/standard deviation of each color channel of every render, each time a new
+/// render is received.
+///
+/// Useful for debugging a render that shows up all black, clipped to white, or otherwise not as
+/// expected. Set `only_on_change` to skip logging when a render's statistics are identical to
+/// those of the previous render.
+///
+/// # example:
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ratatui::RatatuiPlugins;
+/// # use bevy_ratatui_render::{FrameStatisticsPlugin, RatatuiRenderPlugin};
+/// #
+/// App::new().add_plugins((
+///     DefaultPlugins,
+///     RatatuiPlugins::default(),
+///     RatatuiRenderPlugin::new("main", (256, 256)),
+///     FrameStatisticsPlugin {
+///         only_on_change: true,
+///     },
+/// ));
+/// ```
+#[derive(Default)]
+pub struct FrameStatisticsPlugin {
+    /// Skips logging a render whose statistics are identical to those of the previous render.
+    pub only_on_change: bool,
+}
+
+impl Plugin for FrameStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            First,
+            frame_statistics_system_generator(self.only_on_change)
+                .after(receive_rendered_images_system),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ChannelStatistics {
+    min: u8,
+    max: u8,
+    mean: f64,
+    stddev: f64,
+}
+
+impl fmt::Display for ChannelStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{:.1}/{:.1}",
+            self.min, self.max, self.mean, self.stddev
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FrameStatistics([ChannelStatistics; 4]);
+
+impl FrameStatistics {
+    fn from_image(image: &Image) -> Option<Self> {
+        let image = image_to_dynamic(image).ok()?.into_rgba8();
+        let pixel_count = image.width() as f64 * image.height() as f64;
+        if pixel_count == 0. {
+            return None;
+        }
+
+        let mut min = [u8::MAX; 4];
+        let mut max = [u8::MIN; 4];
+        let mut sum = [0.; 4];
+        let mut sum_of_squares = [0.; 4];
+
+        for pixel in image.pixels() {
+            for (channel, &value) in pixel.0.iter().enumerate() {
+                min[channel] = min[channel].min(value);
+                max[channel] = max[channel].max(value);
+                sum[channel] += value as f64;
+                sum_of_squares[channel] += value as f64 * value as f64;
+            }
+        }
+
+        Some(Self(std::array::from_fn(|channel| {
+            let mean = sum[channel] / pixel_count;
+            let variance = (sum_of_squares[channel] / pixel_count - mean * mean).max(0.);
+
+            ChannelStatistics {
+                min: min[channel],
+                max: max[channel],
+                mean,
+                stddev: variance.sqrt(),
+            }
+        })))
+    }
+}
+
+/// Logs statistics for each new render, skipping unchanged renders if `only_on_change` is set.
+///
+/// Frames where no new render was received for a pipe are skipped, rather than recomputing the
+/// statistics of the same image.
+fn frame_statistics_system_generator(
+    only_on_change: bool,
+) -> impl FnMut(Res<RatatuiRenderContext>) {
+    let mut previous = HashMap::<String, FrameStatistics>::new();
+    let mut last_frame_index = HashMap::<String, u64>::new();
+
+    move |ratatui_render| {
+        for (id, render_pipe) in ratatui_render.iter() {
+            let Some(frame_index) = render_pipe.frame_index() else {
+                continue;
+            };
+
+            if last_frame_index.insert(id.clone(), frame_index) == Some(frame_index) {
+                continue;
+            }
+
+            let Some(statistics) = FrameStatistics::from_image(&render_pipe.image) else {
+                continue;
+            };
+
+            if only_on_change && previous.get(id) == Some(&statistics) {
+                continue;
+            }
+
+            let FrameStatistics([r, g, b, a]) = statistics;
+            info!("{id} (min/max/mean/stddev): r {r} g {g} b {b} a {a}");
+
+            previous.insert(id.clone(), statistics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+
+    use super::*;
+
+    fn two_pixels(data: Vec<u8>) -> Image {
+        Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn from_image_computes_each_channel() {
+        let image = two_pixels(vec![0, 10, 100, 255, 200, 30, 100, 255]);
+        let FrameStatistics([r, g, b, a]) = FrameStatistics::from_image(&image).unwrap();
+
+        let channel = |min, max, mean, stddev| ChannelStatistics {
+            min,
+            max,
+            mean,
+            stddev,
+        };
+        assert_eq!(r, channel(0, 200, 100., 100.));
+        assert_eq!(g, channel(10, 30, 20., 10.));
+        assert_eq!(b, channel(100, 100, 100., 0.));
+        assert_eq!(a, channel(255, 255, 255., 0.));
+        assert_eq!(r.to_string(), "0/200/100.0/100.0");
+    }
+
+    #[test]
+    fn identical_images_have_equal_statistics() {
+        let data = vec![0, 10, 100, 255, 200, 30, 100, 255];
+        let statistics = FrameStatistics::from_image(&two_pixels(data.clone()));
+        assert_eq!(statistics, FrameStatistics::from_image(&two_pixels(data)));
+
+        let changed = vec![0, 10, 100, 255, 200, 31, 100, 255];
+        assert_ne!(statistics, FrameStatistics::from_image(&two_pixels(changed)));
+    }
+}
@@ -1,49 +1,232 @@
-use bevy::render::texture::Image;
+use bevy::{log::error, render::texture::Image};
+use image::DynamicImage;
 use ratatui::prelude::*;
 use ratatui_image::{
     picker::{Picker, ProtocolType},
     FilterType, Resize,
 };
 
+use crate::{
+    headless::image_to_dynamic,
+    renderers::{
+        dither::dither_with_config, image_to_ascii, image_to_braille, AsciiConfig, BrailleConfig,
+        ColorConfig, ColorDepth,
+    },
+};
+
+/// Strategy used by `RatatuiRenderWidget` to print a render to the terminal.
+#[derive(Clone, Debug, Default)]
+pub enum RatatuiRenderStrategy {
+    /// Unicode halfblocks, printing two vertically stacked pixels per character cell.
+    #[default]
+    Halfblocks,
+
+    /// Unicode braille patterns, printing a 2x4 grid of dots per character cell that are raised
+    /// or lowered depending on pixel luminance.
+    Braille(BrailleConfig),
+
+    /// Ascii characters picked from a ramp by pixel luminance, printing one pixel per character
+    /// cell. The render is downsampled vertically by half first, as cells are about twice as tall
+    /// as they are wide.
+    Ascii(AsciiConfig),
+}
+
+impl RatatuiRenderStrategy {
+    /// Number of pixels, (width, height), printed in each character cell with this strategy.
+    pub fn cell_pixel_size(&self) -> (u32, u32) {
+        match self {
+            Self::Halfblocks => (1, 2),
+            Self::Braille(_) => (2, 4),
+            Self::Ascii(_) => (1, 2),
+        }
+    }
+}
+
+/// Strategy and color configuration of widgets created without setting them.
+static DEFAULT_STRATEGY: RatatuiRenderStrategy = RatatuiRenderStrategy::Halfblocks;
+static DEFAULT_COLOR_CONFIG: ColorConfig = ColorConfig::DEFAULT;
+
 pub struct RatatuiRenderWidget<'a> {
     image: &'a Image,
-    picker: Picker,
+    strategy: &'a RatatuiRenderStrategy,
+    color_config: &'a ColorConfig,
 }
 
 impl<'a> RatatuiRenderWidget<'a> {
+    /// Creates a widget that prints the image with halfblocks in 24-bit color.
     pub fn new(image: &'a Image) -> Self {
-        let mut picker = Picker::new((1, 2));
-        picker.protocol_type = ProtocolType::Halfblocks;
+        Self {
+            image,
+            strategy: &DEFAULT_STRATEGY,
+            color_config: &DEFAULT_COLOR_CONFIG,
+        }
+    }
 
-        Self { image, picker }
+    /// Sets the strategy used to print the image.
+    pub fn strategy(mut self, strategy: &'a RatatuiRenderStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets how the colors of the image are quantized and dithered.
+    pub fn color_config(mut self, color_config: &'a ColorConfig) -> Self {
+        self.color_config = color_config;
+        self
     }
 }
 
 impl<'a> Widget for RatatuiRenderWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Self { image, mut picker } = self;
+        let Self {
+            image,
+            strategy,
+            color_config,
+        } = self;
 
-        let image = match image.clone().try_into_dynamic() {
+        let image = match image_to_dynamic(image) {
             Ok(image) => image,
-            Err(e) => panic!("failed to create image buffer {e:?}"),
+            Err(e) => {
+                error!("failed to create image buffer {e:?}");
+                return;
+            }
         };
 
-        let image = image.resize(
-            area.width as u32,
-            area.height as u32 * 2,
-            FilterType::Nearest,
-        );
+        match strategy {
+            RatatuiRenderStrategy::Halfblocks if color_config.depth == ColorDepth::TrueColor => {
+                render_halfblocks(image, area, buf)
+            }
+            RatatuiRenderStrategy::Halfblocks => {
+                render_quantized_halfblocks(image, color_config, area, buf)
+            }
+            RatatuiRenderStrategy::Braille(config) => {
+                render_braille(image, config, color_config, area, buf)
+            }
+            RatatuiRenderStrategy::Ascii(config) => render_ascii(image, config, area, buf),
+        }
+    }
+}
 
-        let render_area = Rect {
-            x: area.x + area.width.saturating_sub(image.width() as u16) / 2,
-            y: area.y + (area.height * 2).saturating_sub(image.height() as u16) / 4,
-            ..area
-        };
+fn render_halfblocks(image: DynamicImage, area: Rect, buf: &mut Buffer) {
+    let mut picker = Picker::new((1, 2));
+    picker.protocol_type = ProtocolType::Halfblocks;
+
+    let image = image.resize(
+        area.width as u32,
+        area.height as u32 * 2,
+        FilterType::Nearest,
+    );
+
+    let render_area = Rect {
+        x: area.x + area.width.saturating_sub(image.width() as u16) / 2,
+        y: area.y + (area.height * 2).saturating_sub(image.height() as u16) / 4,
+        ..area
+    };
+
+    let img_as_halfblocks = picker
+        .new_protocol(image, render_area, Resize::Fit(None))
+        .unwrap();
+
+    ratatui_image::Image::new(img_as_halfblocks.as_ref()).render(render_area, buf);
+}
+
+/// Prints halfblocks directly into the buffer, for color depths that ratatui_image cannot print.
+fn render_quantized_halfblocks(
+    image: DynamicImage,
+    color_config: &ColorConfig,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if area.is_empty() {
+        return;
+    }
+
+    let mut image = image.resize(
+        area.width as u32,
+        area.height as u32 * 2,
+        FilterType::Nearest,
+    );
+    dither_with_config(&mut image, color_config);
+    let image = image.into_rgb8();
+
+    let (width, height) = image.dimensions();
+
+    let x = area.x + area.width.saturating_sub(width as u16) / 2;
+    let y = area.y + (area.height * 2).saturating_sub(height as u16) / 4;
+
+    for row in 0..height.div_ceil(2) {
+        for column in 0..width {
+            let cell = buf.get_mut(x + column as u16, y + row as u16);
+            cell.set_char('▀')
+                .set_fg(color_config.quantize(image.get_pixel(column, row * 2).0));
+
+            if row * 2 + 1 < height {
+                cell.set_bg(color_config.quantize(image.get_pixel(column, row * 2 + 1).0));
+            } else {
+                cell.set_bg(Color::Reset);
+            }
+        }
+    }
+}
+
+fn render_braille(
+    image: DynamicImage,
+    config: &BrailleConfig,
+    color_config: &ColorConfig,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if area.is_empty() {
+        return;
+    }
+
+    let image = image.resize(
+        area.width as u32 * 2,
+        area.height as u32 * 4,
+        FilterType::Nearest,
+    );
+
+    let rows = image_to_braille(&image, config);
+    let width = rows.first().map_or(0, Vec::len) as u16;
+
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(rows.len() as u16) / 2;
+
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, character) in row.iter().enumerate() {
+            buf.get_mut(x + column_index as u16, y + row_index as u16)
+                .set_char(*character)
+                .set_fg(color_config.convert(config.foreground))
+                .set_bg(color_config.convert(config.background));
+        }
+    }
+}
+
+fn render_ascii(image: DynamicImage, config: &AsciiConfig, area: Rect, buf: &mut Buffer) {
+    if area.is_empty() {
+        return;
+    }
+
+    let image = image.resize(
+        area.width as u32,
+        area.height as u32 * 2,
+        FilterType::Nearest,
+    );
+    let image = image.resize_exact(
+        image.width(),
+        image.height().div_ceil(2),
+        FilterType::Triangle,
+    );
+
+    let rows = image_to_ascii(&image, config);
+    let width = image.width() as u16;
 
-        let img_as_halfblocks = picker
-            .new_protocol(image, render_area, Resize::Fit(None))
-            .unwrap();
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(rows.len() as u16) / 2;
 
-        ratatui_image::Image::new(img_as_halfblocks.as_ref()).render(render_area, buf);
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, character) in row.chars().enumerate() {
+            buf.get_mut(x + column_index as u16, y + row_index as u16)
+                .set_char(character);
+        }
     }
 }